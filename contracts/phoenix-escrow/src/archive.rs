@@ -0,0 +1,59 @@
+use cosmwasm_std::{Deps, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+use crate::msg::{CompletedAuctionResponse, ListCompletedAuctionsResponse};
+use crate::state::{Auction, CompletedAuction, AUCTIONS, COMPLETED_AUCTIONS, WATCHERS};
+
+// Move a closed auction out of AUCTIONS so only open and unsettled ones stay
+// there. Its watchlist entries go too, as there is nothing left to notify.
+pub fn archive_auction(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    completed: &CompletedAuction,
+) -> StdResult<()> {
+    AUCTIONS.remove(storage, auction_id)?;
+    let watchers = WATCHERS
+        .prefix(auction_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for watcher in watchers {
+        WATCHERS.remove(storage, (auction_id, &watcher));
+    }
+    COMPLETED_AUCTIONS.save(storage, auction_id, completed)
+}
+
+// Look an auction up in either store
+pub fn load_any_auction(storage: &dyn Storage, auction_id: u64) -> StdResult<Auction> {
+    match AUCTIONS.may_load(storage, auction_id)? {
+        Some(auction) => Ok(auction),
+        None => Ok(COMPLETED_AUCTIONS.load(storage, auction_id)?.auction),
+    }
+}
+
+pub fn completed_response(id: u64, completed: CompletedAuction) -> CompletedAuctionResponse {
+    CompletedAuctionResponse {
+        id,
+        auction: completed.auction,
+        winner: completed.winner,
+        gross: completed.gross,
+        fee: completed.fee,
+        net: completed.net,
+        settled_at: completed.settled_at,
+    }
+}
+
+pub fn query_list_completed_auctions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListCompletedAuctionsResponse> {
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let auctions = COMPLETED_AUCTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, completed)| completed_response(id, completed)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListCompletedAuctionsResponse { auctions })
+}
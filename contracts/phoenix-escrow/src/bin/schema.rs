@@ -0,0 +1,14 @@
+use cosmwasm_schema::write_api;
+
+use phoenix_escrow::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+
+// Writes schema/phoenix-escrow.json, the input for the TypeScript client codegen
+fn main() {
+    write_api! {
+        instantiate: InstantiateMsg,
+        execute: ExecuteMsg,
+        query: QueryMsg,
+        migrate: MigrateMsg,
+        sudo: SudoMsg,
+    }
+}
@@ -0,0 +1,144 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+
+use crate::error::ContractError;
+use crate::events::{ConfigUpdated, FeeScheduleUpdated, PhoenixEvent};
+use crate::fees::validate_fee_schedule;
+use crate::msg::ConfigUpdate;
+use crate::roles::ensure_role;
+use crate::state::{FeeSchedule, PendingFeeSchedule, Role, CONFIG, PENDING_FEE_SCHEDULE, POOL_STATE};
+
+// Anything other than a plain rate cut waits out the timelock
+fn is_fee_increase(old: &FeeSchedule, new: &FeeSchedule) -> bool {
+    new.buyer_fee_bps > old.buyer_fee_bps
+        || new.seller_fee_bps > old.seller_fee_bps
+        || new.tiers != old.tiers
+}
+
+// Promote an announced fee change once its effective time has passed
+pub fn apply_pending_fee_schedule(storage: &mut dyn Storage, now: u64) -> StdResult<()> {
+    if let Some(pending) = PENDING_FEE_SCHEDULE.may_load(storage)? {
+        if pending.effective_at <= now {
+            CONFIG.update(storage, |mut config| -> StdResult<_> {
+                config.fee_schedule = pending.fee_schedule;
+                Ok(config)
+            })?;
+            PENDING_FEE_SCHEDULE.remove(storage);
+        }
+    }
+    Ok(())
+}
+
+// Fee schedule in force at `now`, for queries that cannot write the promotion
+pub fn effective_fee_schedule(storage: &dyn Storage, now: u64) -> StdResult<FeeSchedule> {
+    match PENDING_FEE_SCHEDULE.may_load(storage)? {
+        Some(pending) if pending.effective_at <= now => Ok(pending.fee_schedule),
+        _ => Ok(CONFIG.load(storage)?.fee_schedule),
+    }
+}
+
+// Fee fields and accepted denoms need the fee manager, the KYC flag needs a KYC officer.
+// The fee timelock can only be raised.
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let ConfigUpdate {
+        fee_schedule,
+        fee_timelock,
+        require_kyc,
+        insurance_developer,
+        accepted_denoms,
+    } = update;
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut response = Response::new().add_attribute("action", "update_config");
+    let mut updated = ConfigUpdated::default();
+
+    if let Some(fee_timelock) = fee_timelock {
+        ensure_role(deps.storage, &info.sender, Role::FeeManager)?;
+        if fee_timelock < config.fee_timelock {
+            return Err(ContractError::InvalidConfig {
+                reason: "fee_timelock cannot be lowered".to_string(),
+            });
+        }
+        response = response
+            .add_attribute("old_fee_timelock", config.fee_timelock.to_string())
+            .add_attribute("new_fee_timelock", fee_timelock.to_string());
+        config.fee_timelock = fee_timelock;
+        updated.fee_timelock = Some(fee_timelock);
+    }
+
+    if let Some(fee_schedule) = fee_schedule {
+        ensure_role(deps.storage, &info.sender, Role::FeeManager)?;
+        validate_fee_schedule(&fee_schedule)?;
+
+        response = response
+            .add_attribute("old_buyer_fee_bps", config.fee_schedule.buyer_fee_bps.to_string())
+            .add_attribute("new_buyer_fee_bps", fee_schedule.buyer_fee_bps.to_string())
+            .add_attribute("old_seller_fee_bps", config.fee_schedule.seller_fee_bps.to_string())
+            .add_attribute("new_seller_fee_bps", fee_schedule.seller_fee_bps.to_string())
+            .add_attribute("old_fee_tiers", config.fee_schedule.tiers.len().to_string())
+            .add_attribute("new_fee_tiers", fee_schedule.tiers.len().to_string());
+
+        let mut event = FeeScheduleUpdated {
+            buyer_fee_bps: fee_schedule.buyer_fee_bps,
+            seller_fee_bps: fee_schedule.seller_fee_bps,
+            tiers: fee_schedule.tiers.len() as u64,
+            effective_at: env.block.time.seconds(),
+        };
+        if config.fee_timelock > 0 && is_fee_increase(&config.fee_schedule, &fee_schedule) {
+            let effective_at = env.block.time.seconds() + config.fee_timelock;
+            event.effective_at = effective_at;
+            PENDING_FEE_SCHEDULE.save(
+                deps.storage,
+                &PendingFeeSchedule {
+                    fee_schedule,
+                    effective_at,
+                },
+            )?;
+            response = response.add_attribute("fee_change_effective_at", effective_at.to_string());
+        } else {
+            // Cuts apply at once and supersede any announced increase
+            config.fee_schedule = fee_schedule;
+            PENDING_FEE_SCHEDULE.remove(deps.storage);
+        }
+        response = response.add_event(event.to_event());
+    }
+
+    if let Some(require_kyc) = require_kyc {
+        ensure_role(deps.storage, &info.sender, Role::KycOfficer)?;
+        response = response
+            .add_attribute("old_require_kyc", config.require_kyc.to_string())
+            .add_attribute("new_require_kyc", require_kyc.to_string());
+        config.require_kyc = require_kyc;
+        updated.require_kyc = Some(require_kyc);
+    }
+
+    if let Some(insurance_developer) = insurance_developer {
+        ensure_role(deps.storage, &info.sender, Role::FeeManager)?;
+        let developer = deps.api.addr_validate(&insurance_developer)?;
+        let mut pool_state = POOL_STATE.load(deps.storage)?;
+        response = response
+            .add_attribute("old_insurance_developer", pool_state.developer.as_str())
+            .add_attribute("new_insurance_developer", developer.as_str());
+        updated.insurance_developer = Some(developer.clone());
+        pool_state.developer = developer;
+        POOL_STATE.save(deps.storage, &pool_state)?;
+    }
+
+    if let Some(accepted_denoms) = accepted_denoms {
+        ensure_role(deps.storage, &info.sender, Role::FeeManager)?;
+        response = response
+            .add_attribute("old_accepted_denoms", config.accepted_denoms.join(","))
+            .add_attribute("new_accepted_denoms", accepted_denoms.join(","));
+        updated.accepted_denoms = Some(accepted_denoms.clone());
+        config.accepted_denoms = accepted_denoms;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+    if updated != ConfigUpdated::default() {
+        response = response.add_event(updated.to_event());
+    }
+    Ok(response)
+}
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,9 +7,24 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
     
+    #[error("No pending admin")]
+    NoPendingAdmin {},
+    
+    #[error("Contract paused: {scope}")]
+    Paused { scope: String },
+    
+    #[error("Cannot migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+    
+    #[error("Cannot downgrade from {stored} to {current}")]
+    CannotDowngrade { stored: String, current: String },
+    
     #[error("Auction not found")]
     AuctionNotFound {},
     
@@ -27,6 +43,12 @@ pub enum ContractError {
     #[error("Auction not active")]
     AuctionNotActive {},
     
+    #[error("Auction not ended")]
+    AuctionNotEnded {},
+    
+    #[error("Auction not sold")]
+    AuctionNotSold {},
+    
     #[error("Insufficient funds")]
     InsufficientFunds {},
     
@@ -36,6 +58,75 @@ pub enum ContractError {
     #[error("Auction already has bids")]
     AuctionHasBids {},
     
+    #[error("Invalid fee schedule")]
+    InvalidFeeSchedule {},
+    
+    #[error("Invalid config: {reason}")]
+    InvalidConfig { reason: String },
+    
+    #[error("Fee split weights must be positive, unique and sum to 10000")]
+    InvalidFeeSplit {},
+    
+    #[error("No fees to withdraw")]
+    NothingToWithdraw {},
+    
+    #[error("Auction not completed")]
+    AuctionNotCompleted {},
+    
+    #[error("Rating must be between 1 and 5")]
+    InvalidRating {},
+    
+    #[error("Feedback already left for this auction")]
+    AlreadyRated {},
+    
+    #[error("Bidder does not meet the seller's reputation requirement")]
+    ReputationTooLow {},
+    
+    #[error("Denom {denom} is not accepted")]
+    DenomNotAccepted { denom: String },
+    
+    // Governance errors
+    #[error("Governance is not configured")]
+    GovernanceNotConfigured {},
+    
+    #[error("Proposal not found")]
+    ProposalNotFound {},
+    
+    #[error("Voting is closed")]
+    VotingClosed {},
+    
+    #[error("Voting period has not ended")]
+    VotingOpen {},
+    
+    #[error("Already voted")]
+    AlreadyVoted {},
+    
+    #[error("Proposals may only change protocol parameters")]
+    ProposalMsgNotAllowed {},
+    
+    // Rewards errors
+    #[error("Rewards are not configured")]
+    RewardsNotConfigured {},
+    
+    #[error("No rewards to claim")]
+    NothingToClaim {},
+    
+    #[error("Reward emission cap reached for this epoch")]
+    EpochCapReached {},
+    
+    // Insurance errors
+    #[error("Shipping insurance is not active yet")]
+    InsuranceNotActive {},
+    
+    #[error("Insurance pool cannot cover this policy")]
+    PoolInsolvent {},
+    
+    #[error("No active insurance policy")]
+    NoActivePolicy {},
+    
+    #[error("Insurance claim pending")]
+    ClaimPending {},
+    
     // KYC errors
     #[error("KYC verification required")]
     KycRequired {},
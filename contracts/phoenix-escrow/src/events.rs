@@ -0,0 +1,845 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{attr, Addr, Attribute, Event, StdError, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{PauseScope, Role, VoteOption};
+
+// Typed events for indexers. Every auction state transition, and every admin,
+// pause, config and governance change, emits one of these next to the
+// free-form attributes. Within an EVENT_VERSION names and
+// attribute keys are fixed; changing them means bumping the version.
+pub const EVENT_VERSION: &str = "1";
+
+pub trait PhoenixEvent: Sized {
+    const NAME: &'static str;
+
+    fn attributes(&self) -> Vec<Attribute>;
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self>;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("version", EVENT_VERSION)
+            .add_attributes(self.attributes())
+    }
+
+    // Accepts the event as it appears in a tx result, where the chain
+    // prefixes the type with "wasm-"
+    fn from_event(event: &Event) -> StdResult<Self> {
+        let ty = event.ty.strip_prefix("wasm-").unwrap_or(&event.ty);
+        if ty != Self::NAME {
+            return Err(StdError::generic_err(format!(
+                "expected {} event, got {}",
+                Self::NAME,
+                ty
+            )));
+        }
+        let attributes = EventAttributes(&event.attributes);
+        let version = attributes.get("version")?;
+        if version != EVENT_VERSION {
+            return Err(StdError::generic_err(format!(
+                "unsupported {} event version {}",
+                Self::NAME,
+                version
+            )));
+        }
+        Self::from_attributes(&attributes)
+    }
+}
+
+pub struct EventAttributes<'a>(&'a [Attribute]);
+
+impl<'a> EventAttributes<'a> {
+    fn find(&self, key: &str) -> Option<&'a str> {
+        self.0
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    pub fn get(&self, key: &str) -> StdResult<&'a str> {
+        self.find(key)
+            .ok_or_else(|| StdError::generic_err(format!("missing event attribute {}", key)))
+    }
+
+    pub fn parse<T: FromStr>(&self, key: &str) -> StdResult<T> {
+        self.get(key)?
+            .parse()
+            .map_err(|_| StdError::generic_err(format!("invalid event attribute {}", key)))
+    }
+
+    pub fn parse_opt<T: FromStr>(&self, key: &str) -> StdResult<Option<T>> {
+        match self.find(key) {
+            Some(_) => self.parse(key).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn addr(&self, key: &str) -> StdResult<Addr> {
+        self.get(key).map(Addr::unchecked)
+    }
+
+    pub fn addr_opt(&self, key: &str) -> StdResult<Option<Addr>> {
+        Ok(self.find(key).map(Addr::unchecked))
+    }
+}
+
+// Optional fields are left out of the event when unset
+fn push_opt(attributes: &mut Vec<Attribute>, key: &str, value: &Option<impl ToString>) {
+    if let Some(value) = value {
+        attributes.push(attr(key, value.to_string()));
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionCreated {
+    pub auction_id: u64,
+    pub seller: Addr,
+    pub item_id: String,
+    pub denom: String,
+    pub starting_price: Uint128,
+    pub reserve_price: Option<Uint128>,
+    pub buy_now_price: Option<Uint128>,
+    pub ends_at: u64,
+}
+
+impl PhoenixEvent for AuctionCreated {
+    const NAME: &'static str = "phoenix_auction_created";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("seller", &self.seller),
+            attr("item_id", &self.item_id),
+            attr("denom", &self.denom),
+            attr("starting_price", self.starting_price),
+        ];
+        push_opt(&mut attributes, "reserve_price", &self.reserve_price);
+        push_opt(&mut attributes, "buy_now_price", &self.buy_now_price);
+        attributes.push(attr("ends_at", self.ends_at.to_string()));
+        attributes
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AuctionCreated {
+            auction_id: attributes.parse("auction_id")?,
+            seller: attributes.addr("seller")?,
+            item_id: attributes.get("item_id")?.to_string(),
+            denom: attributes.get("denom")?.to_string(),
+            starting_price: attributes.parse("starting_price")?,
+            reserve_price: attributes.parse_opt("reserve_price")?,
+            buy_now_price: attributes.parse_opt("buy_now_price")?,
+            ends_at: attributes.parse("ends_at")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BidPlaced {
+    pub auction_id: u64,
+    pub bidder: Addr,
+    pub amount: Uint128,
+    pub fee: Uint128,
+    pub insured: bool,
+}
+
+impl PhoenixEvent for BidPlaced {
+    const NAME: &'static str = "phoenix_bid_placed";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("bidder", &self.bidder),
+            attr("amount", self.amount),
+            attr("fee", self.fee),
+            attr("insured", self.insured.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(BidPlaced {
+            auction_id: attributes.parse("auction_id")?,
+            bidder: attributes.addr("bidder")?,
+            amount: attributes.parse("amount")?,
+            fee: attributes.parse("fee")?,
+            insured: attributes.parse("insured")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Outbid {
+    pub auction_id: u64,
+    pub previous_bidder: Addr,
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
+impl PhoenixEvent for Outbid {
+    const NAME: &'static str = "phoenix_outbid";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("previous_bidder", &self.previous_bidder),
+            attr("bidder", &self.bidder),
+            attr("amount", self.amount),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(Outbid {
+            auction_id: attributes.parse("auction_id")?,
+            previous_bidder: attributes.addr("previous_bidder")?,
+            bidder: attributes.addr("bidder")?,
+            amount: attributes.parse("amount")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionBought {
+    pub auction_id: u64,
+    pub buyer: Addr,
+    pub price: Uint128,
+    pub fee: Uint128,
+    pub insured: bool,
+}
+
+impl PhoenixEvent for AuctionBought {
+    const NAME: &'static str = "phoenix_auction_bought";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("buyer", &self.buyer),
+            attr("price", self.price),
+            attr("fee", self.fee),
+            attr("insured", self.insured.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AuctionBought {
+            auction_id: attributes.parse("auction_id")?,
+            buyer: attributes.addr("buyer")?,
+            price: attributes.parse("price")?,
+            fee: attributes.parse("fee")?,
+            insured: attributes.parse("insured")?,
+        })
+    }
+}
+
+// With the reserve met the winner still has to release funds; otherwise the
+// auction is closed unsold and any bid refunded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionEnded {
+    pub auction_id: u64,
+    pub reserve_met: bool,
+    pub winner: Option<Addr>,
+    pub amount: Option<Uint128>,
+}
+
+impl PhoenixEvent for AuctionEnded {
+    const NAME: &'static str = "phoenix_auction_ended";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("reserve_met", self.reserve_met.to_string()),
+        ];
+        push_opt(&mut attributes, "winner", &self.winner);
+        push_opt(&mut attributes, "amount", &self.amount);
+        attributes
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AuctionEnded {
+            auction_id: attributes.parse("auction_id")?,
+            reserve_met: attributes.parse("reserve_met")?,
+            winner: attributes.addr_opt("winner")?,
+            amount: attributes.parse_opt("amount")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionCancelled {
+    pub auction_id: u64,
+    pub seller: Addr,
+}
+
+impl PhoenixEvent for AuctionCancelled {
+    const NAME: &'static str = "phoenix_auction_cancelled";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("seller", &self.seller),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AuctionCancelled {
+            auction_id: attributes.parse("auction_id")?,
+            seller: attributes.addr("seller")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimFiled {
+    pub auction_id: u64,
+    pub buyer: Addr,
+}
+
+impl PhoenixEvent for ClaimFiled {
+    const NAME: &'static str = "phoenix_claim_filed";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("buyer", &self.buyer),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(ClaimFiled {
+            auction_id: attributes.parse("auction_id")?,
+            buyer: attributes.addr("buyer")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimResolved {
+    pub auction_id: u64,
+    pub loss_in_transit: bool,
+    // Paid to the buyer from the insurance pool on loss in transit
+    pub payout: Option<Uint128>,
+}
+
+impl PhoenixEvent for ClaimResolved {
+    const NAME: &'static str = "phoenix_claim_resolved";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("loss_in_transit", self.loss_in_transit.to_string()),
+        ];
+        push_opt(&mut attributes, "payout", &self.payout);
+        attributes
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(ClaimResolved {
+            auction_id: attributes.parse("auction_id")?,
+            loss_in_transit: attributes.parse("loss_in_transit")?,
+            payout: attributes.parse_opt("payout")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionSettled {
+    pub auction_id: u64,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub denom: String,
+    pub gross: Uint128,
+    pub fee: Uint128,
+    pub net: Uint128,
+}
+
+impl PhoenixEvent for AuctionSettled {
+    const NAME: &'static str = "phoenix_auction_settled";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("seller", &self.seller),
+            attr("buyer", &self.buyer),
+            attr("denom", &self.denom),
+            attr("gross", self.gross),
+            attr("fee", self.fee),
+            attr("net", self.net),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AuctionSettled {
+            auction_id: attributes.parse("auction_id")?,
+            seller: attributes.addr("seller")?,
+            buyer: attributes.addr("buyer")?,
+            denom: attributes.get("denom")?.to_string(),
+            gross: attributes.parse("gross")?,
+            fee: attributes.parse("fee")?,
+            net: attributes.parse("net")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeedbackLeft {
+    pub auction_id: u64,
+    pub from: Addr,
+    pub rated: Addr,
+    pub rating: u8,
+}
+
+impl PhoenixEvent for FeedbackLeft {
+    const NAME: &'static str = "phoenix_feedback_left";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("auction_id", self.auction_id.to_string()),
+            attr("from", &self.from),
+            attr("rated", &self.rated),
+            attr("rating", self.rating.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(FeedbackLeft {
+            auction_id: attributes.parse("auction_id")?,
+            from: attributes.addr("from")?,
+            rated: attributes.addr("rated")?,
+            rating: attributes.parse("rating")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminProposed {
+    pub admin: Addr,
+    pub proposed: Addr,
+}
+
+impl PhoenixEvent for AdminProposed {
+    const NAME: &'static str = "phoenix_admin_proposed";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![attr("admin", &self.admin), attr("proposed", &self.proposed)]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AdminProposed {
+            admin: attributes.addr("admin")?,
+            proposed: attributes.addr("proposed")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminAccepted {
+    pub previous_admin: Option<Addr>,
+    pub admin: Addr,
+}
+
+impl PhoenixEvent for AdminAccepted {
+    const NAME: &'static str = "phoenix_admin_accepted";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        push_opt(&mut attributes, "previous_admin", &self.previous_admin);
+        attributes.push(attr("admin", &self.admin));
+        attributes
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AdminAccepted {
+            previous_admin: attributes.addr_opt("previous_admin")?,
+            admin: attributes.addr("admin")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminRenounced {
+    pub previous_admin: Addr,
+}
+
+impl PhoenixEvent for AdminRenounced {
+    const NAME: &'static str = "phoenix_admin_renounced";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![attr("previous_admin", &self.previous_admin)]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(AdminRenounced {
+            previous_admin: attributes.addr("previous_admin")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleGranted {
+    pub role: Role,
+    pub address: Addr,
+}
+
+impl PhoenixEvent for RoleGranted {
+    const NAME: &'static str = "phoenix_role_granted";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![attr("role", self.role.as_str()), attr("address", &self.address)]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(RoleGranted {
+            role: attributes.parse("role")?,
+            address: attributes.addr("address")?,
+        })
+    }
+}
+
+// Also emitted for each role an outgoing admin loses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub address: Addr,
+}
+
+impl PhoenixEvent for RoleRevoked {
+    const NAME: &'static str = "phoenix_role_revoked";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![attr("role", self.role.as_str()), attr("address", &self.address)]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(RoleRevoked {
+            role: attributes.parse("role")?,
+            address: attributes.addr("address")?,
+        })
+    }
+}
+
+// Scopes travel as a comma-separated list
+fn scope_list(scopes: &[PauseScope]) -> String {
+    scopes.iter().map(|scope| scope.as_str()).collect::<Vec<_>>().join(",")
+}
+
+fn parse_scope_list(attributes: &EventAttributes) -> StdResult<Vec<PauseScope>> {
+    attributes
+        .get("scopes")?
+        .split(',')
+        .map(|scope| scope.parse())
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Paused {
+    pub scopes: Vec<PauseScope>,
+    pub by: Addr,
+}
+
+impl PhoenixEvent for Paused {
+    const NAME: &'static str = "phoenix_paused";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![attr("scopes", scope_list(&self.scopes)), attr("by", &self.by)]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(Paused {
+            scopes: parse_scope_list(attributes)?,
+            by: attributes.addr("by")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Unpaused {
+    pub scopes: Vec<PauseScope>,
+    pub by: Addr,
+}
+
+impl PhoenixEvent for Unpaused {
+    const NAME: &'static str = "phoenix_unpaused";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![attr("scopes", scope_list(&self.scopes)), attr("by", &self.by)]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(Unpaused {
+            scopes: parse_scope_list(attributes)?,
+            by: attributes.addr("by")?,
+        })
+    }
+}
+
+// New values of the fields an UpdateConfig changed. Fee schedule changes get
+// their own event.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ConfigUpdated {
+    pub fee_timelock: Option<u64>,
+    pub require_kyc: Option<bool>,
+    pub insurance_developer: Option<Addr>,
+    pub accepted_denoms: Option<Vec<String>>,
+}
+
+// An empty list accepts any denom. Attribute values cannot be empty, so it is
+// written as "*", which no denom can be.
+const ANY_DENOM: &str = "*";
+
+impl PhoenixEvent for ConfigUpdated {
+    const NAME: &'static str = "phoenix_config_updated";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        push_opt(&mut attributes, "fee_timelock", &self.fee_timelock);
+        push_opt(&mut attributes, "require_kyc", &self.require_kyc);
+        push_opt(&mut attributes, "insurance_developer", &self.insurance_developer);
+        if let Some(denoms) = &self.accepted_denoms {
+            let value = if denoms.is_empty() {
+                ANY_DENOM.to_string()
+            } else {
+                denoms.join(",")
+            };
+            attributes.push(attr("accepted_denoms", value));
+        }
+        attributes
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(ConfigUpdated {
+            fee_timelock: attributes.parse_opt("fee_timelock")?,
+            require_kyc: attributes.parse_opt("require_kyc")?,
+            insurance_developer: attributes.addr_opt("insurance_developer")?,
+            accepted_denoms: attributes.find("accepted_denoms").map(|denoms| match denoms {
+                ANY_DENOM => vec![],
+                denoms => denoms.split(',').map(String::from).collect(),
+            }),
+        })
+    }
+}
+
+// A rate cut takes effect at once; anything else waits out the fee timelock
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeScheduleUpdated {
+    pub buyer_fee_bps: u64,
+    pub seller_fee_bps: u64,
+    pub tiers: u64,
+    pub effective_at: u64,
+}
+
+impl PhoenixEvent for FeeScheduleUpdated {
+    const NAME: &'static str = "phoenix_fee_schedule_updated";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("buyer_fee_bps", self.buyer_fee_bps.to_string()),
+            attr("seller_fee_bps", self.seller_fee_bps.to_string()),
+            attr("tiers", self.tiers.to_string()),
+            attr("effective_at", self.effective_at.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(FeeScheduleUpdated {
+            buyer_fee_bps: attributes.parse("buyer_fee_bps")?,
+            seller_fee_bps: attributes.parse("seller_fee_bps")?,
+            tiers: attributes.parse("tiers")?,
+            effective_at: attributes.parse("effective_at")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeSplitUpdated {
+    pub recipients: u64,
+    pub dust_recipient: Addr,
+}
+
+impl PhoenixEvent for FeeSplitUpdated {
+    const NAME: &'static str = "phoenix_fee_split_updated";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("recipients", self.recipients.to_string()),
+            attr("dust_recipient", &self.dust_recipient),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(FeeSplitUpdated {
+            recipients: attributes.parse("recipients")?,
+            dust_recipient: attributes.addr("dust_recipient")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Addr,
+    pub expires_at: u64,
+}
+
+impl PhoenixEvent for ProposalCreated {
+    const NAME: &'static str = "phoenix_proposal_created";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("proposal_id", self.proposal_id.to_string()),
+            attr("proposer", &self.proposer),
+            attr("expires_at", self.expires_at.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(ProposalCreated {
+            proposal_id: attributes.parse("proposal_id")?,
+            proposer: attributes.addr("proposer")?,
+            expires_at: attributes.parse("expires_at")?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Addr,
+    pub vote: VoteOption,
+    pub weight: Uint128,
+}
+
+impl PhoenixEvent for VoteCast {
+    const NAME: &'static str = "phoenix_vote_cast";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("proposal_id", self.proposal_id.to_string()),
+            attr("voter", &self.voter),
+            attr("vote", self.vote.as_str()),
+            attr("weight", self.weight),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(VoteCast {
+            proposal_id: attributes.parse("proposal_id")?,
+            voter: attributes.addr("voter")?,
+            vote: attributes.parse("vote")?,
+            weight: attributes.parse("weight")?,
+        })
+    }
+}
+
+// Tallied after the voting period: a passed proposal is executed, any other
+// is rejected
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalClosed {
+    pub proposal_id: u64,
+    pub passed: bool,
+}
+
+impl PhoenixEvent for ProposalClosed {
+    const NAME: &'static str = "phoenix_proposal_closed";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr("proposal_id", self.proposal_id.to_string()),
+            attr("passed", self.passed.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> StdResult<Self> {
+        Ok(ProposalClosed {
+            proposal_id: attributes.parse("proposal_id")?,
+            passed: attributes.parse("passed")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_round_trip() {
+        let ended = AuctionEnded {
+            auction_id: 3,
+            reserve_met: false,
+            winner: None,
+            amount: None,
+        };
+        let mut event = ended.to_event();
+        assert_eq!(event.attributes.len(), 3);
+
+        // Indexers see the chain's "wasm-" prefix
+        event.ty = format!("wasm-{}", event.ty);
+        assert_eq!(AuctionEnded::from_event(&event).unwrap(), ended);
+        assert!(AuctionCancelled::from_event(&event).is_err());
+
+        event.attributes[0].value = "2".to_string();
+        assert!(AuctionEnded::from_event(&event).is_err());
+    }
+
+    fn round_trip<E: PhoenixEvent + PartialEq + std::fmt::Debug>(typed: E) {
+        let mut event = typed.to_event();
+        // The chain rejects empty attribute values
+        assert!(event.attributes.iter().all(|attribute| !attribute.value.is_empty()));
+        event.ty = format!("wasm-{}", event.ty);
+        assert_eq!(E::from_event(&event).unwrap(), typed);
+    }
+
+    #[test]
+    fn test_admin_and_governance_events_round_trip() {
+        let admin = Addr::unchecked("admin");
+        round_trip(AdminAccepted {
+            previous_admin: None,
+            admin: admin.clone(),
+        });
+        round_trip(RoleRevoked {
+            role: Role::KycOfficer,
+            address: admin.clone(),
+        });
+        round_trip(Paused {
+            scopes: vec![PauseScope::Bidding, PauseScope::Settlement],
+            by: admin.clone(),
+        });
+        // An emptied denom list means any denom is accepted
+        round_trip(ConfigUpdated {
+            require_kyc: Some(true),
+            accepted_denoms: Some(vec![]),
+            ..ConfigUpdated::default()
+        });
+        round_trip(ConfigUpdated {
+            insurance_developer: Some(admin.clone()),
+            accepted_denoms: Some(vec!["urlusd".to_string(), "uatom".to_string()]),
+            ..ConfigUpdated::default()
+        });
+        round_trip(FeeScheduleUpdated {
+            buyer_fee_bps: 50,
+            seller_fee_bps: 110,
+            tiers: 2,
+            effective_at: 86_400,
+        });
+        round_trip(VoteCast {
+            proposal_id: 4,
+            voter: admin,
+            vote: VoteOption::Abstain,
+            weight: Uint128::new(600),
+        });
+        round_trip(ProposalClosed {
+            proposal_id: 4,
+            passed: false,
+        });
+
+        let mut event = Outbid {
+            auction_id: 0,
+            previous_bidder: Addr::unchecked("alice"),
+            bidder: Addr::unchecked("bob"),
+            amount: Uint128::new(1500),
+        }
+        .to_event();
+        assert_eq!(event.ty, "phoenix_outbid");
+        event.ty = "outbid".to_string();
+        assert!(Outbid::from_event(&event).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use cosmwasm_std::{Deps, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+use crate::msg::{AuctionResponse, ListAuctionsResponse};
+use crate::state::{AuctionStatus, AUCTIONS, EXPIRY_QUEUE};
+
+pub const DEFAULT_FINALIZE_LIMIT: u32 = 10;
+pub const MAX_FINALIZE_LIMIT: u32 = 30;
+
+pub fn enqueue_expiry(storage: &mut dyn Storage, auction_id: u64, ends_at: u64) -> StdResult<()> {
+    EXPIRY_QUEUE.save(storage, (ends_at, auction_id), &true)
+}
+
+// Called whenever an auction stops being Active
+pub fn dequeue_expiry(storage: &mut dyn Storage, auction_id: u64, ends_at: u64) {
+    EXPIRY_QUEUE.remove(storage, (ends_at, auction_id));
+}
+
+// Ids of up to `limit` queued auctions that ended at or before `now`
+pub fn expired_auctions(storage: &dyn Storage, now: u64, limit: u32) -> StdResult<Vec<u64>> {
+    let limit = limit.min(MAX_FINALIZE_LIMIT) as usize;
+    EXPIRY_QUEUE
+        .keys(
+            storage,
+            None,
+            Some(Bound::inclusive((now, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|key| key.map(|(_, auction_id)| auction_id))
+        .collect()
+}
+
+// Paged on the (ends_at, id) queue key, since many auctions can end at once
+pub fn query_ending_between(
+    deps: Deps,
+    from: u64,
+    to: u64,
+    start_after: Option<(u64, u64)>,
+    limit: Option<u32>,
+) -> StdResult<ListAuctionsResponse> {
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let start = match start_after {
+        Some(cursor) if cursor >= (from, 0) => Bound::exclusive(cursor),
+        _ => Bound::inclusive((from, 0)),
+    };
+
+    let auctions = EXPIRY_QUEUE
+        .keys(
+            deps.storage,
+            Some(start),
+            Some(Bound::inclusive((to, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|key| {
+            let (_, id) = key?;
+            let auction = AUCTIONS.load(deps.storage, id)?;
+            Ok(AuctionResponse { id, auction })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListAuctionsResponse { auctions })
+}
+
+// Queue every Active auction. Returns the number queued.
+pub fn backfill_expiry_queue(storage: &mut dyn Storage) -> StdResult<u64> {
+    let active = AUCTIONS
+        .range(storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, auction)| auction.status == AuctionStatus::Active)
+        })
+        .map(|item| item.map(|(id, auction)| (id, auction.ends_at)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (id, ends_at) in &active {
+        enqueue_expiry(storage, *id, *ends_at)?;
+    }
+    Ok(active.len() as u64)
+}
@@ -0,0 +1,112 @@
+use cosmwasm_std::{Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::events::{FeedbackLeft, PhoenixEvent};
+use crate::msg::{FeedbackEntry, RatingsResponse};
+use crate::state::{AuctionStatus, Feedback, AUCTIONS, COMPLETED_AUCTIONS, FEEDBACK, RATINGS};
+
+pub const MIN_RATING: u8 = 1;
+pub const MAX_RATING: u8 = 5;
+
+// Buyer and seller of a completed auction each rate the other once
+pub fn execute_leave_feedback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    rating: u8,
+    comment_hash: String,
+) -> Result<Response, ContractError> {
+    if !(MIN_RATING..=MAX_RATING).contains(&rating) {
+        return Err(ContractError::InvalidRating {});
+    }
+
+    // Settled auctions are only found in the archive
+    let completed = match COMPLETED_AUCTIONS.may_load(deps.storage, auction_id)? {
+        Some(completed) => completed,
+        None if AUCTIONS.has(deps.storage, auction_id) => {
+            return Err(ContractError::AuctionNotCompleted {})
+        }
+        None => return Err(ContractError::AuctionNotFound {}),
+    };
+    let auction = completed.auction;
+    if auction.status != AuctionStatus::Completed {
+        return Err(ContractError::AuctionNotCompleted {});
+    }
+    let buyer = completed.winner.ok_or(ContractError::AuctionNotSold {})?;
+
+    let rated = if info.sender == auction.creator {
+        buyer
+    } else if info.sender == buyer {
+        auction.creator
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+    if FEEDBACK.has(deps.storage, (&rated, auction_id)) {
+        return Err(ContractError::AlreadyRated {});
+    }
+
+    FEEDBACK.save(
+        deps.storage,
+        (&rated, auction_id),
+        &Feedback {
+            from: info.sender.clone(),
+            rating,
+            comment_hash,
+            timestamp: env.block.time.seconds(),
+        },
+    )?;
+    RATINGS.update(deps.storage, &rated, |summary| -> StdResult<_> {
+        let mut summary = summary.unwrap_or_default();
+        summary.count += 1;
+        summary.total += rating as u64;
+        Ok(summary)
+    })?;
+
+    let event = FeedbackLeft {
+        auction_id,
+        from: info.sender.clone(),
+        rated: rated.clone(),
+        rating,
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_attribute("action", "leave_feedback")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("from", info.sender)
+        .add_attribute("rated", rated)
+        .add_attribute("rating", rating.to_string()))
+}
+
+// Aggregate rating of `address` with its feedback entries paged by auction id
+pub fn query_ratings(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RatingsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let summary = RATINGS.may_load(deps.storage, &address)?.unwrap_or_default();
+
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let feedback = FEEDBACK
+        .prefix(&address)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(auction_id, feedback)| FeedbackEntry { auction_id, feedback }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let average = if summary.count == 0 {
+        None
+    } else {
+        Some(Decimal::from_ratio(summary.total, summary.count))
+    };
+    Ok(RatingsResponse {
+        address,
+        count: summary.count,
+        average,
+        feedback,
+    })
+}
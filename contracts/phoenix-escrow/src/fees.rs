@@ -0,0 +1,353 @@
+use cosmwasm_std::{Addr, Api, Coin, Deps, Env, Order, StdError, StdResult, Storage, Uint128};
+
+use crate::archive::load_any_auction;
+use crate::config::effective_fee_schedule;
+use crate::error::ContractError;
+use crate::insurance::{credit_pool, BPS_DENOMINATOR, PREMIUM_BPS};
+use crate::msg::{AccruedFeesResponse, FeeRecipientMsg, SimulateFeesResponse};
+use crate::state::{FeeRecipient, FeeSchedule, FeeSplit, ACCRUED_FEES, VOLUMES};
+
+// Hard cap on either side of the fee: 10%
+pub const MAX_FEE_BPS: u64 = 1_000;
+
+pub fn validate_fee_schedule(schedule: &FeeSchedule) -> Result<(), ContractError> {
+    if schedule.buyer_fee_bps > MAX_FEE_BPS || schedule.seller_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFeeSchedule {});
+    }
+
+    // Tiers must be strictly ascending by volume, discounts at most 100%
+    let mut previous: Option<Uint128> = None;
+    for tier in &schedule.tiers {
+        if tier.discount_bps > BPS_DENOMINATOR as u64 {
+            return Err(ContractError::InvalidFeeSchedule {});
+        }
+        if let Some(previous) = previous {
+            if tier.min_volume <= previous {
+                return Err(ContractError::InvalidFeeSchedule {});
+            }
+        }
+        previous = Some(tier.min_volume);
+    }
+
+    Ok(())
+}
+
+// Discount of the highest tier the volume qualifies for
+pub fn discount_bps(schedule: &FeeSchedule, volume: Uint128) -> u64 {
+    schedule
+        .tiers
+        .iter()
+        .rev()
+        .find(|tier| volume >= tier.min_volume)
+        .map(|tier| tier.discount_bps)
+        .unwrap_or(0)
+}
+
+pub fn effective_bps(base_bps: u64, discount_bps: u64) -> u64 {
+    base_bps * (BPS_DENOMINATOR as u64 - discount_bps) / BPS_DENOMINATOR as u64
+}
+
+pub fn buyer_fee_bps(storage: &dyn Storage, schedule: &FeeSchedule, buyer: &Addr) -> StdResult<u64> {
+    let volume = VOLUMES.may_load(storage, buyer)?.unwrap_or_default();
+    Ok(effective_bps(schedule.buyer_fee_bps, discount_bps(schedule, volume)))
+}
+
+pub fn seller_fee_bps(storage: &dyn Storage, schedule: &FeeSchedule, seller: &Addr) -> StdResult<u64> {
+    let volume = VOLUMES.may_load(storage, seller)?.unwrap_or_default();
+    Ok(effective_bps(schedule.seller_fee_bps, discount_bps(schedule, volume)))
+}
+
+pub fn fee_for(amount: Uint128, bps: u64) -> Uint128 {
+    amount.multiply_ratio(bps, BPS_DENOMINATOR)
+}
+
+// Split a bid payment into (bid, buyer fee, premium).
+// Funds cover the bid plus the buyer fee and, if insured, the premium on top;
+// rounding dust stays with the buyer fee.
+pub fn split_payment(paid: Uint128, buyer_bps: u64, insure: bool) -> (Uint128, Uint128, Uint128) {
+    let premium_bps = if insure { PREMIUM_BPS } else { 0 };
+    let amount = paid.multiply_ratio(
+        BPS_DENOMINATOR,
+        BPS_DENOMINATOR + buyer_bps as u128 + premium_bps,
+    );
+    let premium = amount.multiply_ratio(premium_bps, BPS_DENOMINATOR);
+    (amount, paid - amount - premium, premium)
+}
+
+// Smallest payment that split_payment turns back into a bid of `amount`
+pub fn payment_for_bid(amount: Uint128, buyer_bps: u64, insure: bool) -> Uint128 {
+    let premium_bps = if insure { PREMIUM_BPS } else { 0 };
+    let total_bps = BPS_DENOMINATOR + buyer_bps as u128 + premium_bps;
+    let paid = amount.multiply_ratio(total_bps, BPS_DENOMINATOR);
+    if paid.multiply_ratio(BPS_DENOMINATOR, total_bps) < amount {
+        paid + Uint128::one()
+    } else {
+        paid
+    }
+}
+
+// Count settled volume towards both parties' tiers
+pub fn record_volume(
+    storage: &mut dyn Storage,
+    buyer: &Addr,
+    seller: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    for address in [buyer, seller] {
+        VOLUMES.update(storage, address, |volume| -> StdResult<_> {
+            Ok(volume.unwrap_or_default() + amount)
+        })?;
+    }
+    Ok(())
+}
+
+pub fn build_fee_split(
+    api: &dyn Api,
+    recipients: Vec<FeeRecipientMsg>,
+    dust_recipient: String,
+) -> Result<FeeSplit, ContractError> {
+    let mut validated: Vec<FeeRecipient> = Vec::with_capacity(recipients.len());
+    let mut total_weight = 0u64;
+    for recipient in recipients {
+        let address = api.addr_validate(&recipient.address)?;
+        if recipient.weight_bps == 0 || validated.iter().any(|r| r.address == address) {
+            return Err(ContractError::InvalidFeeSplit {});
+        }
+        total_weight += recipient.weight_bps;
+        validated.push(FeeRecipient {
+            address,
+            weight_bps: recipient.weight_bps,
+        });
+    }
+    if total_weight != BPS_DENOMINATOR as u64 {
+        return Err(ContractError::InvalidFeeSplit {});
+    }
+
+    Ok(FeeSplit {
+        recipients: validated,
+        dust_recipient: api.addr_validate(&dust_recipient)?,
+    })
+}
+
+// Divide a settled fee between the recipients. Shares are accrued for later
+// withdrawal; those addressed to the contract itself go to the insurance pool.
+// Returns whether the pool crossed its activation threshold.
+pub fn distribute_fee(
+    storage: &mut dyn Storage,
+    contract: &Addr,
+    split: &FeeSplit,
+    denom: &str,
+    fee: Uint128,
+) -> StdResult<bool> {
+    let mut shares: Vec<(&Addr, Uint128)> = split
+        .recipients
+        .iter()
+        .map(|r| (&r.address, fee.multiply_ratio(r.weight_bps, BPS_DENOMINATOR)))
+        .collect();
+    let distributed: Uint128 = shares.iter().map(|(_, share)| *share).sum();
+    shares.push((&split.dust_recipient, fee - distributed));
+
+    let mut activated = false;
+    for (address, share) in shares {
+        if share.is_zero() {
+            continue;
+        }
+        if address == contract {
+            activated |= credit_pool(storage, denom, share)?;
+        } else {
+            accrue_fee(storage, address, denom, share)?;
+        }
+    }
+
+    Ok(activated)
+}
+
+pub fn accrue_fee(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    ACCRUED_FEES.update(storage, (address, denom), |accrued| -> StdResult<_> {
+        Ok(accrued.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
+
+pub fn query_accrued_fees(deps: Deps, address: String) -> StdResult<AccruedFeesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let fees = ACCRUED_FEES
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AccruedFeesResponse { fees })
+}
+
+// Fees for `address` buying at the given price. For an auction the seller's
+// own discount is applied; for a bare amount `address` is used for both sides.
+pub fn query_simulate_fees(
+    deps: Deps,
+    env: Env,
+    auction_id: Option<u64>,
+    amount: Option<Uint128>,
+    address: String,
+) -> StdResult<SimulateFeesResponse> {
+    let fee_schedule = effective_fee_schedule(deps.storage, env.block.time.seconds())?;
+    let address = deps.api.addr_validate(&address)?;
+
+    let (amount, seller) = match (auction_id, amount) {
+        (Some(auction_id), _) => {
+            let auction = load_any_auction(deps.storage, auction_id)?;
+            let amount = auction
+                .highest_bid
+                .map(|bid| bid.amount)
+                .or(auction.buy_now_price)
+                .unwrap_or(auction.starting_price);
+            (amount, auction.creator)
+        }
+        (None, Some(amount)) => (amount, address.clone()),
+        (None, None) => {
+            return Err(StdError::generic_err(
+                "either auction_id or amount is required",
+            ))
+        }
+    };
+
+    let buyer_fee_bps = buyer_fee_bps(deps.storage, &fee_schedule, &address)?;
+    let seller_fee_bps = seller_fee_bps(deps.storage, &fee_schedule, &seller)?;
+
+    Ok(SimulateFeesResponse {
+        amount,
+        buyer_fee_bps,
+        buyer_fee: fee_for(amount, buyer_fee_bps),
+        seller_fee_bps,
+        seller_fee: fee_for(amount, seller_fee_bps),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::VolumeTier;
+    use cosmwasm_std::testing::MockApi;
+
+    fn schedule() -> FeeSchedule {
+        FeeSchedule {
+            buyer_fee_bps: 50,
+            seller_fee_bps: 60,
+            tiers: vec![
+                VolumeTier { min_volume: Uint128::new(10_000), discount_bps: 2_500 },
+                VolumeTier { min_volume: Uint128::new(100_000), discount_bps: 5_000 },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_volume_tiers() {
+        let schedule = schedule();
+        assert_eq!(discount_bps(&schedule, Uint128::new(9_999)), 0);
+        assert_eq!(discount_bps(&schedule, Uint128::new(10_000)), 2_500);
+        assert_eq!(discount_bps(&schedule, Uint128::new(500_000)), 5_000);
+        assert_eq!(effective_bps(schedule.seller_fee_bps, 5_000), 30);
+    }
+
+    #[test]
+    fn test_split_payment() {
+        // 1000 bid + 5 buyer fee + 20 premium
+        let (amount, fee, premium) = split_payment(Uint128::new(1025), 50, true);
+        assert_eq!(amount, Uint128::new(1000));
+        assert_eq!(fee, Uint128::new(5));
+        assert_eq!(premium, Uint128::new(20));
+
+        let (amount, fee, premium) = split_payment(Uint128::new(1005), 50, false);
+        assert_eq!((amount, fee, premium), (Uint128::new(1000), Uint128::new(5), Uint128::zero()));
+
+        // Rounding up keeps the full bid when the fee has a fractional part
+        assert_eq!(payment_for_bid(Uint128::new(1000), 50, true), Uint128::new(1025));
+        let paid = payment_for_bid(Uint128::new(999), 50, false);
+        assert_eq!(paid, Uint128::new(1004));
+        assert_eq!(split_payment(paid, 50, false).0, Uint128::new(999));
+    }
+
+    #[test]
+    fn test_validate_fee_schedule() {
+        assert!(validate_fee_schedule(&schedule()).is_ok());
+
+        let mut unordered = schedule();
+        unordered.tiers.reverse();
+        assert_eq!(
+            validate_fee_schedule(&unordered),
+            Err(ContractError::InvalidFeeSchedule {})
+        );
+
+        let mut too_high = schedule();
+        too_high.seller_fee_bps = MAX_FEE_BPS + 1;
+        assert_eq!(
+            validate_fee_schedule(&too_high),
+            Err(ContractError::InvalidFeeSchedule {})
+        );
+    }
+
+    #[test]
+    fn test_build_fee_split() {
+        let api = MockApi::default();
+        let recipient = |address: &str, weight_bps| FeeRecipientMsg {
+            address: address.to_string(),
+            weight_bps,
+        };
+
+        let split = build_fee_split(
+            &api,
+            vec![recipient("security", 5_000), recipient("maintenance", 3_000), recipient("community", 2_000)],
+            "security".to_string(),
+        )
+        .unwrap();
+        assert_eq!(split.recipients.len(), 3);
+
+        let short = build_fee_split(&api, vec![recipient("security", 9_999)], "security".to_string());
+        assert_eq!(short, Err(ContractError::InvalidFeeSplit {}));
+
+        let duplicate = build_fee_split(
+            &api,
+            vec![recipient("security", 5_000), recipient("security", 5_000)],
+            "security".to_string(),
+        );
+        assert_eq!(duplicate, Err(ContractError::InvalidFeeSplit {}));
+    }
+
+    #[test]
+    fn test_distribute_fee_dust() {
+        let mut storage = cosmwasm_std::testing::MockStorage::new();
+        let split = FeeSplit {
+            recipients: vec![
+                FeeRecipient { address: Addr::unchecked("security"), weight_bps: 5_000 },
+                FeeRecipient { address: Addr::unchecked("maintenance"), weight_bps: 3_000 },
+                FeeRecipient { address: Addr::unchecked("community"), weight_bps: 2_000 },
+            ],
+            dust_recipient: Addr::unchecked("community"),
+        };
+
+        let activated = distribute_fee(
+            &mut storage,
+            &Addr::unchecked("contract"),
+            &split,
+            "urlusd",
+            Uint128::new(101),
+        )
+        .unwrap();
+        assert!(!activated);
+        let accrued = |address: &str| {
+            ACCRUED_FEES
+                .load(&storage, (&Addr::unchecked(address), "urlusd"))
+                .unwrap()
+                .u128()
+        };
+        assert_eq!(accrued("security"), 50);
+        assert_eq!(accrued("maintenance"), 30);
+        assert_eq!(accrued("community"), 21);
+    }
+}
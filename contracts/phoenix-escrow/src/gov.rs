@@ -0,0 +1,372 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::events::{PhoenixEvent, ProposalClosed, ProposalCreated, VoteCast};
+use crate::insurance::BPS_DENOMINATOR;
+use crate::msg::{ExecuteMsg, ListProposalsResponse, ProposalResponse, ReceiveMsg};
+use crate::roles::ensure_admin;
+use crate::state::{
+    Ballot, GovConfig, Proposal, ProposalStatus, VoteOption, BALLOTS, GOV_CONFIG, PROPOSALS,
+    PROPOSAL_COUNT,
+};
+
+// Governance executes passed proposals by calling this contract with its own
+// address as sender, so the contract needs the fee manager role to apply them.
+// Proposals are limited to parameter changes; admin, role, pause and
+// withdrawal messages stay with their holders.
+
+pub fn execute_configure_governance(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: String,
+    voting_period: u64,
+    quorum_bps: u64,
+    threshold_bps: u64,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    if voting_period == 0
+        || quorum_bps > BPS_DENOMINATOR as u64
+        || threshold_bps == 0
+        || threshold_bps > BPS_DENOMINATOR as u64
+    {
+        return Err(ContractError::InvalidConfig {
+            reason: "invalid governance parameters".to_string(),
+        });
+    }
+
+    let gov_config = GovConfig {
+        token: deps.api.addr_validate(&token)?,
+        voting_period,
+        quorum_bps,
+        threshold_bps,
+    };
+    GOV_CONFIG.save(deps.storage, &gov_config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "configure_governance")
+        .add_attribute("token", gov_config.token)
+        .add_attribute("voting_period", voting_period.to_string())
+        .add_attribute("quorum_bps", quorum_bps.to_string())
+        .add_attribute("threshold_bps", threshold_bps.to_string()))
+}
+
+fn ensure_parameter_change(msg: &ExecuteMsg) -> Result<(), ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig { .. } | ExecuteMsg::UpdateFeeSplit { .. } => Ok(()),
+        _ => Err(ContractError::ProposalMsgNotAllowed {}),
+    }
+}
+
+// Any holder of the voting token may propose
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    msgs: Vec<ExecuteMsg>,
+) -> Result<Response, ContractError> {
+    let gov_config = GOV_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::GovernanceNotConfigured {})?;
+    msgs.iter().try_for_each(ensure_parameter_change)?;
+
+    let balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+        &gov_config.token,
+        &Cw20QueryMsg::Balance {
+            address: info.sender.to_string(),
+        },
+    )?;
+    if balance.balance.is_zero() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let id = PROPOSAL_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    let proposal = Proposal {
+        proposer: info.sender.clone(),
+        title,
+        description,
+        msgs,
+        yes: Uint128::zero(),
+        no: Uint128::zero(),
+        abstain: Uint128::zero(),
+        expires_at: env.block.time.seconds() + gov_config.voting_period,
+        status: ProposalStatus::Open,
+    };
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+    PROPOSAL_COUNT.save(deps.storage, &(id + 1))?;
+
+    let event = ProposalCreated {
+        proposal_id: id,
+        proposer: info.sender.clone(),
+        expires_at: proposal.expires_at,
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_attribute("action", "propose")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposer", info.sender)
+        .add_attribute("expires_at", proposal.expires_at.to_string()))
+}
+
+// Votes arrive as CW20 sends; the tokens stay locked until the vote closes
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let gov_config = GOV_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::GovernanceNotConfigured {})?;
+    if info.sender != gov_config.token {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let voter = deps.api.addr_validate(&wrapper.sender)?;
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Vote { proposal_id, vote } => {
+            cast_vote(deps, env, voter, proposal_id, vote, wrapper.amount)
+        }
+    }
+}
+
+fn cast_vote(
+    deps: DepsMut,
+    env: Env,
+    voter: Addr,
+    proposal_id: u64,
+    vote: VoteOption,
+    weight: Uint128,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::ProposalNotFound {})?;
+    if proposal.status != ProposalStatus::Open || env.block.time.seconds() >= proposal.expires_at {
+        return Err(ContractError::VotingClosed {});
+    }
+    if BALLOTS.has(deps.storage, (proposal_id, &voter)) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+
+    match vote {
+        VoteOption::Yes => proposal.yes += weight,
+        VoteOption::No => proposal.no += weight,
+        VoteOption::Abstain => proposal.abstain += weight,
+    }
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    BALLOTS.save(
+        deps.storage,
+        (proposal_id, &voter),
+        &Ballot {
+            vote,
+            weight,
+            reclaimed: false,
+        },
+    )?;
+
+    let event = VoteCast {
+        proposal_id,
+        voter: voter.clone(),
+        vote,
+        weight,
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_attribute("action", "vote")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", voter)
+        .add_attribute("vote", vote.as_str())
+        .add_attribute("weight", weight))
+}
+
+// Tally a proposal after its voting period; passed proposals dispatch their
+// messages back into this contract
+pub fn execute_execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let gov_config = GOV_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::GovernanceNotConfigured {})?;
+    let mut proposal = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::ProposalNotFound {})?;
+    if proposal.status != ProposalStatus::Open {
+        return Err(ContractError::VotingClosed {});
+    }
+    if env.block.time.seconds() < proposal.expires_at {
+        return Err(ContractError::VotingOpen {});
+    }
+
+    let token_info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(&gov_config.token, &Cw20QueryMsg::TokenInfo {})?;
+    let passed = is_passed(&gov_config, &proposal, token_info.total_supply);
+
+    let event = ProposalClosed {
+        proposal_id,
+        passed,
+    };
+    let mut response = Response::new()
+        .add_event(event.to_event())
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("passed", passed.to_string());
+
+    if passed {
+        for msg in &proposal.msgs {
+            response = response.add_message(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(msg)?,
+                funds: vec![],
+            });
+        }
+        proposal.status = ProposalStatus::Executed;
+    } else {
+        proposal.status = ProposalStatus::Rejected;
+    }
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(response)
+}
+
+fn is_passed(gov_config: &GovConfig, proposal: &Proposal, total_supply: Uint128) -> bool {
+    let turnout = proposal.yes + proposal.no + proposal.abstain;
+    if total_supply.is_zero()
+        || turnout.multiply_ratio(BPS_DENOMINATOR, total_supply)
+            < Uint128::from(gov_config.quorum_bps)
+    {
+        return false;
+    }
+
+    let decisive = proposal.yes + proposal.no;
+    !decisive.is_zero()
+        && proposal.yes.multiply_ratio(BPS_DENOMINATOR, decisive)
+            >= Uint128::from(gov_config.threshold_bps)
+}
+
+// Return locked voting tokens once the proposal is no longer open for votes
+pub fn execute_reclaim_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let gov_config = GOV_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::GovernanceNotConfigured {})?;
+    let proposal = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::ProposalNotFound {})?;
+    if env.block.time.seconds() < proposal.expires_at {
+        return Err(ContractError::VotingOpen {});
+    }
+
+    let mut ballot = BALLOTS
+        .may_load(deps.storage, (proposal_id, &info.sender))?
+        .ok_or(ContractError::NothingToWithdraw {})?;
+    if ballot.reclaimed {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+    ballot.reclaimed = true;
+    BALLOTS.save(deps.storage, (proposal_id, &info.sender), &ballot)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: gov_config.token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount: ballot.weight,
+            })?,
+            funds: vec![],
+        })
+        .add_attribute("action", "reclaim_vote")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("amount", ballot.weight))
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListProposalsResponse> {
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let proposals = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, proposal)| ProposalResponse { id, proposal }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListProposalsResponse { proposals })
+}
+
+impl VoteOption {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VoteOption::Yes => "yes",
+            VoteOption::No => "no",
+            VoteOption::Abstain => "abstain",
+        }
+    }
+}
+
+impl FromStr for VoteOption {
+    type Err = StdError;
+
+    fn from_str(vote: &str) -> StdResult<Self> {
+        match vote {
+            "yes" => Ok(VoteOption::Yes),
+            "no" => Ok(VoteOption::No),
+            "abstain" => Ok(VoteOption::Abstain),
+            _ => Err(StdError::generic_err(format!("unknown vote {}", vote))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(yes: u128, no: u128, abstain: u128) -> Proposal {
+        Proposal {
+            proposer: Addr::unchecked("proposer"),
+            title: "Lower seller fee".to_string(),
+            description: String::new(),
+            msgs: vec![],
+            yes: Uint128::new(yes),
+            no: Uint128::new(no),
+            abstain: Uint128::new(abstain),
+            expires_at: 0,
+            status: ProposalStatus::Open,
+        }
+    }
+
+    #[test]
+    fn test_quorum_and_threshold() {
+        let gov_config = GovConfig {
+            token: Addr::unchecked("phnx"),
+            voting_period: 604_800,
+            quorum_bps: 2_000,
+            threshold_bps: 5_001,
+        };
+        let supply = Uint128::new(1_000);
+
+        // 19% turnout misses quorum
+        assert!(!is_passed(&gov_config, &proposal(190, 0, 0), supply));
+        // Abstain counts towards quorum, not the threshold
+        assert!(is_passed(&gov_config, &proposal(60, 40, 100), supply));
+        // An even split does not pass
+        assert!(!is_passed(&gov_config, &proposal(100, 100, 0), supply));
+    }
+}
@@ -1,27 +1,657 @@
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{to_json_binary, Addr, CosmosMsg, StdResult, WasmMsg};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdError, StdResult, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
 
-use crate::msg::ExecuteMsg;
+use crate::fees::payment_for_bid;
+use crate::insurance::premium_for;
+use crate::msg::{
+    AccruedFeesResponse, AuctionParams, AuctionResponse, BallotResponse, BidHistoryResponse, ConfigResponse,
+    ConfigUpdate, ExecuteMsg, FeeRecipientMsg, GovConfigResponse, ListAuctionsResponse,
+    ListCompletedAuctionsResponse, ListProposalsResponse, PendingRewardsResponse, PolicyResponse,
+    PoolStateResponse, ProposalResponse, QueryMsg, RatingsResponse, ReceiveMsg,
+    ReputationResponse, RewardsConfigResponse, RolesResponse, SimulateFeesResponse,
+    WatchersResponse,
+};
+use crate::state::{AuctionStatus, FeeSchedule, PauseScope, ReputationRequirement, Role, VoteOption};
 
-/// CwTemplateContract is a wrapper around Addr that provides a lot of helpers
-/// for working with this.
+/// EscrowContract is a wrapper around the escrow address with typed
+/// builders for every execute message and typed queries, so other
+/// contracts can integrate without hand-crafting JSON.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
-pub struct CwTemplateContract(pub Addr);
+pub struct EscrowContract(pub Addr);
 
-impl CwTemplateContract {
+impl EscrowContract {
     pub fn addr(&self) -> Addr {
         self.0.clone()
     }
 
     pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
         let msg = to_json_binary(&msg.into())?;
         Ok(WasmMsg::Execute {
             contract_addr: self.addr().into(),
             msg,
+            funds,
+        }
+        .into())
+    }
+
+    // Auctions
+
+    pub fn create_auction(&self, params: AuctionParams) -> StdResult<CosmosMsg> {
+        self.call(params)
+    }
+
+    /// Bid `amount`, attaching the buyer fee and, if insured, the premium on
+    /// top. The escrow records whoever sends the message as the bidder and
+    /// charges that address's fee tier, so `sender` must be the account that
+    /// will send it; a contract bidding for its users passes its own address.
+    pub fn place_bid(
+        &self,
+        querier: &QuerierWrapper,
+        auction_id: u64,
+        sender: impl Into<String>,
+        amount: Uint128,
+        insure: bool,
+    ) -> StdResult<CosmosMsg> {
+        let auction = self.auction(querier, auction_id)?.auction;
+        let fees = self.simulate_fees(querier, None, Some(amount), sender)?;
+        let paid = payment_for_bid(amount, fees.buyer_fee_bps, insure);
+        self.call_with_funds(
+            ExecuteMsg::PlaceBid {
+                auction_id,
+                insure: Some(insure),
+            },
+            coins(paid.u128(), auction.denom),
+        )
+    }
+
+    /// Buy at the buy-now price, attaching price, buyer fee and any premium.
+    /// As with `place_bid`, `sender` is the account that will send the message.
+    pub fn buy_now(
+        &self,
+        querier: &QuerierWrapper,
+        auction_id: u64,
+        sender: impl Into<String>,
+        insure: bool,
+    ) -> StdResult<CosmosMsg> {
+        let auction = self.auction(querier, auction_id)?.auction;
+        let price = auction
+            .buy_now_price
+            .ok_or_else(|| StdError::generic_err("auction has no buy-now price"))?;
+        let fees = self.simulate_fees(querier, None, Some(price), sender)?;
+        let premium = if insure { premium_for(price) } else { Uint128::zero() };
+        self.call_with_funds(
+            ExecuteMsg::BuyNow {
+                auction_id,
+                insure: Some(insure),
+            },
+            coins((price + fees.buyer_fee + premium).u128(), auction.denom),
+        )
+    }
+
+    pub fn end_auction(&self, auction_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::EndAuction { auction_id })
+    }
+
+    pub fn finalize_expired(&self, limit: Option<u32>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::FinalizeExpired { limit })
+    }
+
+    pub fn cancel_auction(&self, auction_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::CancelAuction { auction_id })
+    }
+
+    pub fn release_funds(&self, auction_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ReleaseFunds { auction_id })
+    }
+
+    pub fn watch(&self, auction_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Watch { auction_id })
+    }
+
+    pub fn unwatch(&self, auction_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Unwatch { auction_id })
+    }
+
+    pub fn leave_feedback(
+        &self,
+        auction_id: u64,
+        rating: u8,
+        comment_hash: impl Into<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::LeaveFeedback {
+            auction_id,
+            rating,
+            comment_hash: comment_hash.into(),
+        })
+    }
+
+    // Fees and config
+
+    pub fn withdraw_fees(&self, denom: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::WithdrawFees {
+            denom: denom.into(),
+        })
+    }
+
+    pub fn update_config(&self, update: ConfigUpdate) -> StdResult<CosmosMsg> {
+        self.call(update)
+    }
+
+    pub fn update_fee_split(
+        &self,
+        recipients: Vec<FeeRecipientMsg>,
+        dust_recipient: impl Into<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::UpdateFeeSplit {
+            recipients,
+            dust_recipient: dust_recipient.into(),
+        })
+    }
+
+    // Shipping insurance claims
+
+    pub fn file_claim(&self, auction_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::FileClaim { auction_id })
+    }
+
+    pub fn resolve_claim(&self, auction_id: u64, loss_in_transit: bool) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ResolveClaim {
+            auction_id,
+            loss_in_transit,
+        })
+    }
+
+    // Admin and roles
+
+    pub fn propose_admin(&self, address: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ProposeAdmin {
+            address: address.into(),
+        })
+    }
+
+    pub fn accept_admin(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::AcceptAdmin {})
+    }
+
+    pub fn renounce_admin(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RenounceAdmin {})
+    }
+
+    pub fn grant_role(&self, role: Role, address: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::GrantRole {
+            role,
+            address: address.into(),
+        })
+    }
+
+    pub fn revoke_role(&self, role: Role, address: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RevokeRole {
+            role,
+            address: address.into(),
+        })
+    }
+
+    pub fn pause(&self, scopes: Vec<PauseScope>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Pause { scopes })
+    }
+
+    pub fn unpause(&self, scopes: Vec<PauseScope>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Unpause { scopes })
+    }
+
+    pub fn verify_user(&self, address: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::VerifyUser {
+            address: address.into(),
+        })
+    }
+
+    pub fn revoke_verification(&self, address: impl Into<String>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::RevokeVerification {
+            address: address.into(),
+        })
+    }
+
+    // Governance
+
+    pub fn configure_governance(
+        &self,
+        token: impl Into<String>,
+        voting_period: u64,
+        quorum_bps: u64,
+        threshold_bps: u64,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ConfigureGovernance {
+            token: token.into(),
+            voting_period,
+            quorum_bps,
+            threshold_bps,
+        })
+    }
+
+    pub fn propose(
+        &self,
+        title: impl Into<String>,
+        description: impl Into<String>,
+        msgs: Vec<ExecuteMsg>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Propose {
+            title: title.into(),
+            description: description.into(),
+            msgs,
+        })
+    }
+
+    /// Votes are cast by sending `amount` voting tokens to the escrow, so
+    /// this message goes to the token contract rather than the escrow
+    pub fn vote(
+        &self,
+        token: impl Into<String>,
+        amount: Uint128,
+        proposal_id: u64,
+        vote: VoteOption,
+    ) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: token.into(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: self.addr().into(),
+                amount,
+                msg: to_json_binary(&ReceiveMsg::Vote { proposal_id, vote })?,
+            })?,
             funds: vec![],
         }
         .into())
     }
+
+    pub fn execute_proposal(&self, proposal_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Execute { proposal_id })
+    }
+
+    pub fn reclaim_vote(&self, proposal_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ReclaimVote { proposal_id })
+    }
+
+    // Trading rewards
+
+    pub fn configure_rewards(
+        &self,
+        token: impl Into<String>,
+        denom: impl Into<String>,
+        reward_bps: u64,
+        epoch_length: u64,
+        epoch_cap: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ConfigureRewards {
+            token: token.into(),
+            denom: denom.into(),
+            reward_bps,
+            epoch_length,
+            epoch_cap,
+        })
+    }
+
+    pub fn claim_rewards(&self) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::ClaimRewards {})
+    }
+
+    // Queries
+
+    pub fn query<T: DeserializeOwned>(&self, querier: &QuerierWrapper, msg: &QueryMsg) -> StdResult<T> {
+        querier.query_wasm_smart(self.addr(), msg)
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<ConfigResponse> {
+        self.query(querier, &QueryMsg::Config {})
+    }
+
+    pub fn auction(&self, querier: &QuerierWrapper, id: u64) -> StdResult<AuctionResponse> {
+        self.query(querier, &QueryMsg::Auction { id })
+    }
+
+    pub fn bid_history(
+        &self,
+        querier: &QuerierWrapper,
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<BidHistoryResponse> {
+        self.query(querier, &QueryMsg::BidHistory { auction_id, start_after, limit })
+    }
+
+    pub fn list_auctions(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        filter_active: Option<bool>,
+    ) -> StdResult<ListAuctionsResponse> {
+        self.query(querier, &QueryMsg::ListAuctions { start_after, limit, filter_active })
+    }
+
+    pub fn list_completed_auctions(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListCompletedAuctionsResponse> {
+        self.query(querier, &QueryMsg::ListCompletedAuctions { start_after, limit })
+    }
+
+    pub fn ending_between(
+        &self,
+        querier: &QuerierWrapper,
+        from: u64,
+        to: u64,
+        start_after: Option<(u64, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<ListAuctionsResponse> {
+        self.query(querier, &QueryMsg::EndingBetween { from, to, start_after, limit })
+    }
+
+    pub fn auctions_by_seller(
+        &self,
+        querier: &QuerierWrapper,
+        seller: impl Into<String>,
+        status: Option<AuctionStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListAuctionsResponse> {
+        let seller = seller.into();
+        self.query(querier, &QueryMsg::AuctionsBySeller { seller, status, start_after, limit })
+    }
+
+    pub fn auctions_by_bidder(
+        &self,
+        querier: &QuerierWrapper,
+        bidder: impl Into<String>,
+        status: Option<AuctionStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListAuctionsResponse> {
+        let bidder = bidder.into();
+        self.query(querier, &QueryMsg::AuctionsByBidder { bidder, status, start_after, limit })
+    }
+
+    pub fn won_auctions(
+        &self,
+        querier: &QuerierWrapper,
+        winner: impl Into<String>,
+        status: Option<AuctionStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListAuctionsResponse> {
+        let winner = winner.into();
+        self.query(querier, &QueryMsg::WonAuctions { winner, status, start_after, limit })
+    }
+
+    pub fn watchers(
+        &self,
+        querier: &QuerierWrapper,
+        auction_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<WatchersResponse> {
+        self.query(querier, &QueryMsg::Watchers { auction_id, start_after, limit })
+    }
+
+    pub fn is_verified(&self, querier: &QuerierWrapper, address: impl Into<String>) -> StdResult<bool> {
+        self.query(querier, &QueryMsg::IsVerified { address: address.into() })
+    }
+
+    pub fn pool_state(&self, querier: &QuerierWrapper) -> StdResult<PoolStateResponse> {
+        self.query(querier, &QueryMsg::PoolState {})
+    }
+
+    pub fn policy(&self, querier: &QuerierWrapper, auction_id: u64) -> StdResult<PolicyResponse> {
+        self.query(querier, &QueryMsg::Policy { auction_id })
+    }
+
+    pub fn simulate_fees(
+        &self,
+        querier: &QuerierWrapper,
+        auction_id: Option<u64>,
+        amount: Option<Uint128>,
+        address: impl Into<String>,
+    ) -> StdResult<SimulateFeesResponse> {
+        let address = address.into();
+        self.query(querier, &QueryMsg::SimulateFees { auction_id, amount, address })
+    }
+
+    pub fn accrued_fees(
+        &self,
+        querier: &QuerierWrapper,
+        address: impl Into<String>,
+    ) -> StdResult<AccruedFeesResponse> {
+        self.query(querier, &QueryMsg::AccruedFees { address: address.into() })
+    }
+
+    pub fn roles(&self, querier: &QuerierWrapper) -> StdResult<RolesResponse> {
+        self.query(querier, &QueryMsg::Roles {})
+    }
+
+    pub fn gov_config(&self, querier: &QuerierWrapper) -> StdResult<GovConfigResponse> {
+        self.query(querier, &QueryMsg::GovConfig {})
+    }
+
+    pub fn proposal(&self, querier: &QuerierWrapper, proposal_id: u64) -> StdResult<ProposalResponse> {
+        self.query(querier, &QueryMsg::Proposal { proposal_id })
+    }
+
+    pub fn list_proposals(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListProposalsResponse> {
+        self.query(querier, &QueryMsg::ListProposals { start_after, limit })
+    }
+
+    pub fn ballot(
+        &self,
+        querier: &QuerierWrapper,
+        proposal_id: u64,
+        voter: impl Into<String>,
+    ) -> StdResult<BallotResponse> {
+        self.query(querier, &QueryMsg::Ballot { proposal_id, voter: voter.into() })
+    }
+
+    pub fn rewards_config(&self, querier: &QuerierWrapper) -> StdResult<RewardsConfigResponse> {
+        self.query(querier, &QueryMsg::RewardsConfig {})
+    }
+
+    pub fn pending_rewards(
+        &self,
+        querier: &QuerierWrapper,
+        address: impl Into<String>,
+    ) -> StdResult<PendingRewardsResponse> {
+        self.query(querier, &QueryMsg::PendingRewards { address: address.into() })
+    }
+
+    pub fn reputation(
+        &self,
+        querier: &QuerierWrapper,
+        address: impl Into<String>,
+    ) -> StdResult<ReputationResponse> {
+        self.query(querier, &QueryMsg::Reputation { address: address.into() })
+    }
+
+    pub fn ratings(
+        &self,
+        querier: &QuerierWrapper,
+        address: impl Into<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<RatingsResponse> {
+        let address = address.into();
+        self.query(querier, &QueryMsg::Ratings { address, start_after, limit })
+    }
+}
+
+/// Builder methods for `ExecuteMsg::CreateAuction`
+impl AuctionParams {
+    pub fn new(
+        item_id: impl Into<String>,
+        denom: impl Into<String>,
+        starting_price: impl Into<Uint128>,
+        duration_hours: u64,
+    ) -> Self {
+        AuctionParams {
+            item_id: item_id.into(),
+            denom: denom.into(),
+            starting_price: starting_price.into(),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours,
+            buyer_requirement: None,
+        }
+    }
+
+    pub fn reserve_price(mut self, reserve_price: impl Into<Uint128>) -> Self {
+        self.reserve_price = Some(reserve_price.into());
+        self
+    }
+
+    pub fn buy_now_price(mut self, buy_now_price: impl Into<Uint128>) -> Self {
+        self.buy_now_price = Some(buy_now_price.into());
+        self
+    }
+
+    pub fn buyer_requirement(mut self, requirement: ReputationRequirement) -> Self {
+        self.buyer_requirement = Some(requirement);
+        self
+    }
+}
+
+impl From<AuctionParams> for ExecuteMsg {
+    fn from(params: AuctionParams) -> Self {
+        ExecuteMsg::CreateAuction {
+            item_id: params.item_id,
+            denom: params.denom,
+            starting_price: params.starting_price,
+            reserve_price: params.reserve_price,
+            buy_now_price: params.buy_now_price,
+            duration_hours: params.duration_hours,
+            buyer_requirement: params.buyer_requirement,
+        }
+    }
+}
+
+/// Builder methods for `ExecuteMsg::UpdateConfig`; unset fields stay unchanged
+impl ConfigUpdate {
+    pub fn fee_schedule(mut self, fee_schedule: FeeSchedule) -> Self {
+        self.fee_schedule = Some(fee_schedule);
+        self
+    }
+
+    pub fn fee_timelock(mut self, fee_timelock: u64) -> Self {
+        self.fee_timelock = Some(fee_timelock);
+        self
+    }
+
+    pub fn require_kyc(mut self, require_kyc: bool) -> Self {
+        self.require_kyc = Some(require_kyc);
+        self
+    }
+
+    pub fn insurance_developer(mut self, developer: impl Into<String>) -> Self {
+        self.insurance_developer = Some(developer.into());
+        self
+    }
+
+    pub fn accepted_denoms(mut self, denoms: Vec<String>) -> Self {
+        self.accepted_denoms = Some(denoms);
+        self
+    }
+}
+
+impl From<ConfigUpdate> for ExecuteMsg {
+    fn from(update: ConfigUpdate) -> Self {
+        ExecuteMsg::UpdateConfig {
+            fee_schedule: update.fee_schedule,
+            fee_timelock: update.fee_timelock,
+            require_kyc: update.require_kyc,
+            insurance_developer: update.insurance_developer,
+            accepted_denoms: update.accepted_denoms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Auction;
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{from_json, ContractResult, SystemResult, WasmQuery};
+
+    #[test]
+    fn test_place_bid_attaches_fees() {
+        let mut querier = MockQuerier::default();
+        querier.update_wasm(|query| {
+            let msg = match query {
+                WasmQuery::Smart { msg, .. } => from_json(msg).unwrap(),
+                _ => panic!("unexpected query"),
+            };
+            let res = match msg {
+                QueryMsg::Auction { id } => to_json_binary(&AuctionResponse {
+                    id,
+                    auction: Auction {
+                        creator: Addr::unchecked("seller"),
+                        item_id: "gold-bar".to_string(),
+                        denom: "urlusd".to_string(),
+                        starting_price: Uint128::new(500),
+                        reserve_price: None,
+                        buy_now_price: None,
+                        ends_at: 0,
+                        bid_count: 0,
+                        highest_bid: None,
+                        status: AuctionStatus::Active,
+                        created_at: 0,
+                        buyer_requirement: None,
+                    },
+                }),
+                QueryMsg::SimulateFees { amount, address, .. } => {
+                    // Fees are quoted for the account sending the bid
+                    assert_eq!(address, "router");
+                    to_json_binary(&SimulateFeesResponse {
+                        amount: amount.unwrap(),
+                        buyer_fee_bps: 50,
+                        buyer_fee: amount.unwrap().multiply_ratio(50u128, 10_000u128),
+                        seller_fee_bps: 0,
+                        seller_fee: Uint128::zero(),
+                    })
+                }
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+        let querier = QuerierWrapper::new(&querier);
+
+        let escrow = EscrowContract(Addr::unchecked("escrow"));
+        let msg = escrow
+            .place_bid(&querier, 0, "router", Uint128::new(1000), true)
+            .unwrap();
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => {
+                assert_eq!(funds, coins(1025, "urlusd"));
+            }
+            _ => panic!("expected a wasm execute"),
+        }
+
+        let err = escrow.buy_now(&querier, 0, "router", false).unwrap_err();
+        assert_eq!(err, StdError::generic_err("auction has no buy-now price"));
+    }
 }
@@ -0,0 +1,160 @@
+use cosmwasm_std::{Deps, Order, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::{PoolBalanceResponse, PoolStateResponse};
+use crate::state::{Policy, PolicyStatus, POLICIES, POOL_BALANCES, POOL_STATE};
+
+// Developer holds a 10% ownership stake in everything credited to the pool
+pub const DEVELOPER_SHARE_BPS: u128 = 1_000;
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// 50,000 RLUSD (6 decimals)
+pub const DEFAULT_ACTIVATION_THRESHOLD: u128 = 50_000_000_000;
+
+// Shipping insurance premium: 2.0% of the covered amount,
+// split 90% to the pool and 10% to the developer
+pub const PREMIUM_BPS: u128 = 200;
+pub const PREMIUM_DEVELOPER_BPS: u128 = 1_000;
+
+// Credit platform fees to the pool.
+// Returns true if this credit pushed the pool over the activation threshold.
+pub fn credit_pool(
+    storage: &mut dyn Storage,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<bool> {
+    if amount.is_zero() {
+        return Ok(false);
+    }
+
+    let mut balance = POOL_BALANCES.may_load(storage, denom)?.unwrap_or_default();
+    balance.total += amount;
+    balance.developer_share += amount.multiply_ratio(DEVELOPER_SHARE_BPS, BPS_DENOMINATOR);
+    POOL_BALANCES.save(storage, denom, &balance)?;
+
+    let mut state = POOL_STATE.load(storage)?;
+    if !state.insurance_active
+        && denom == state.threshold_denom
+        && balance.total >= state.activation_threshold
+    {
+        state.insurance_active = true;
+        POOL_STATE.save(storage, &state)?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+pub fn premium_for(coverage: Uint128) -> Uint128 {
+    coverage.multiply_ratio(PREMIUM_BPS, BPS_DENOMINATOR)
+}
+
+// Open a policy for the current highest bidder, reserving pool funds for the coverage
+pub fn open_policy(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    policy: Policy,
+) -> Result<(), ContractError> {
+    if !POOL_STATE.load(storage)?.insurance_active {
+        return Err(ContractError::InsuranceNotActive {});
+    }
+
+    let mut balance = POOL_BALANCES.may_load(storage, &policy.denom)?.unwrap_or_default();
+    if balance.total < balance.committed + policy.coverage {
+        return Err(ContractError::PoolInsolvent {});
+    }
+    balance.committed += policy.coverage;
+    POOL_BALANCES.save(storage, &policy.denom, &balance)?;
+
+    POLICIES.save(storage, auction_id, &policy)?;
+    Ok(())
+}
+
+// Drop the policy of an outbid or unsuccessful bidder.
+// Returns the premium that has to be refunded alongside the bid.
+pub fn cancel_policy(storage: &mut dyn Storage, auction_id: u64) -> StdResult<Uint128> {
+    let policy = match POLICIES.may_load(storage, auction_id)? {
+        Some(policy) => policy,
+        None => return Ok(Uint128::zero()),
+    };
+
+    release_commitment(storage, &policy)?;
+    POLICIES.remove(storage, auction_id);
+    Ok(policy.premium)
+}
+
+// Settle the policy once the trade completes: the premium is earned by the pool,
+// minus the developer's cut which the caller pays out.
+pub fn close_policy(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    status: PolicyStatus,
+) -> StdResult<Option<Policy>> {
+    let mut policy = match POLICIES.may_load(storage, auction_id)? {
+        Some(policy) => policy,
+        None => return Ok(None),
+    };
+
+    release_commitment(storage, &policy)?;
+
+    let mut balance = POOL_BALANCES.may_load(storage, &policy.denom)?.unwrap_or_default();
+    balance.total += policy.premium - developer_premium_cut(&policy);
+    POOL_BALANCES.save(storage, &policy.denom, &balance)?;
+
+    policy.status = status;
+    POLICIES.save(storage, auction_id, &policy)?;
+    Ok(Some(policy))
+}
+
+pub fn developer_premium_cut(policy: &Policy) -> Uint128 {
+    policy.premium.multiply_ratio(PREMIUM_DEVELOPER_BPS, BPS_DENOMINATOR)
+}
+
+// Pay an approved claim out of the pool
+pub fn pay_claim(storage: &mut dyn Storage, policy: &Policy) -> Result<(), ContractError> {
+    let mut balance = POOL_BALANCES.may_load(storage, &policy.denom)?.unwrap_or_default();
+    if balance.total < policy.coverage {
+        return Err(ContractError::PoolInsolvent {});
+    }
+
+    // The developer's stake shrinks in proportion to the payout
+    let remaining = balance.total - policy.coverage;
+    balance.developer_share = balance.developer_share.multiply_ratio(remaining, balance.total);
+    balance.total = remaining;
+    POOL_BALANCES.save(storage, &policy.denom, &balance)?;
+    Ok(())
+}
+
+fn release_commitment(storage: &mut dyn Storage, policy: &Policy) -> StdResult<()> {
+    if policy.status != PolicyStatus::Active && policy.status != PolicyStatus::Claimed {
+        return Ok(());
+    }
+    let mut balance = POOL_BALANCES.may_load(storage, &policy.denom)?.unwrap_or_default();
+    balance.committed = balance.committed.saturating_sub(policy.coverage);
+    POOL_BALANCES.save(storage, &policy.denom, &balance)?;
+    Ok(())
+}
+
+pub fn query_pool_state(deps: Deps) -> StdResult<PoolStateResponse> {
+    let state = POOL_STATE.load(deps.storage)?;
+
+    let balances = POOL_BALANCES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(denom, balance)| PoolBalanceResponse {
+                denom,
+                total: balance.total,
+                committed: balance.committed,
+                developer_share: balance.developer_share,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PoolStateResponse {
+        balances,
+        developer: state.developer,
+        activation_threshold: state.activation_threshold,
+        threshold_denom: state.threshold_denom,
+        insurance_active: state.insurance_active,
+    })
+}
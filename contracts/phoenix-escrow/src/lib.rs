@@ -1,4 +1,20 @@
+pub mod archive;
+pub mod config;
 pub mod contract;
 pub mod msg;
 pub mod state;
 pub mod error;
+pub mod events;
+pub mod expiry;
+pub mod feedback;
+pub mod fees;
+pub mod gov;
+pub mod helpers;
+pub mod insurance;
+pub mod migrate;
+pub mod pause;
+pub mod reputation;
+pub mod rewards;
+pub mod roles;
+pub mod views;
+pub mod watch;
@@ -0,0 +1,247 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::Map;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::archive::archive_auction;
+use crate::state::{
+    Auction, AuctionStatus, Bid, CompletedAuction, AUCTIONS, BIDS, COMPLETED_AUCTIONS,
+};
+
+// Auction layout written by the deployment package (v1.0.0)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyAuction {
+    pub id: u64,
+    pub item_id: String,
+    pub description: String,
+    pub metal_type: String,
+    pub product_form: String,
+    pub weight: u32,
+    pub starting_price: Uint128,
+    pub reserve_price: Option<Uint128>,
+    pub buy_now_price: Option<Uint128>,
+    pub highest_bid: Option<Uint128>,
+    pub highest_bidder: Option<Addr>,
+    pub seller: Addr,
+    pub status: LegacyAuctionStatus,
+    pub end_time: Timestamp,
+    pub created_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum LegacyAuctionStatus {
+    Active,
+    Ended,
+    Cancelled,
+    Completed,
+}
+
+// Same namespace as AUCTIONS, read with the old layout
+const LEGACY_AUCTIONS: Map<u64, LegacyAuction> = Map::new("auctions");
+
+impl LegacyAuction {
+    // The deployment package recorded bids without escrowing any funds, so a
+    // legacy high bid is kept as history only. Active auctions resume bidding
+    // above it instead of treating it as a refundable deposit.
+    fn into_auction(self, denom: &str) -> (Auction, Option<Bid>) {
+        let legacy_bid = match (self.highest_bidder, self.highest_bid) {
+            (Some(bidder), Some(amount)) => Some(Bid {
+                bidder,
+                amount,
+                fee: Uint128::zero(),
+                timestamp: self.created_at.seconds(),
+            }),
+            _ => None,
+        };
+
+        let status = match self.status {
+            LegacyAuctionStatus::Active => AuctionStatus::Active,
+            LegacyAuctionStatus::Ended => AuctionStatus::Ended,
+            LegacyAuctionStatus::Cancelled => AuctionStatus::Cancelled,
+            LegacyAuctionStatus::Completed => AuctionStatus::Completed,
+        };
+
+        let starting_price = match (&status, &legacy_bid) {
+            (AuctionStatus::Active, Some(bid)) => bid.amount.max(self.starting_price),
+            _ => self.starting_price,
+        };
+
+        let auction = Auction {
+            creator: self.seller,
+            item_id: self.item_id,
+            denom: denom.to_string(),
+            starting_price,
+            reserve_price: self.reserve_price,
+            buy_now_price: self.buy_now_price,
+            ends_at: self.end_time.seconds(),
+            bid_count: legacy_bid.iter().count() as u64,
+            highest_bid: None,
+            status,
+            created_at: self.created_at.seconds(),
+            buyer_requirement: None,
+        };
+        (auction, legacy_bid)
+    }
+}
+
+// Closed auctions belong in the archive. Older versions never recorded how a
+// sale was settled, so a completed one keeps its winner and price but the fee
+// and payout stay zero. Open and unsettled auctions return None.
+fn closed_record(auction: Auction, winning_bid: Option<&Bid>) -> Option<CompletedAuction> {
+    let closed_at = auction.ends_at;
+    match auction.status {
+        AuctionStatus::Completed => Some(CompletedAuction {
+            winner: winning_bid.map(|bid| bid.bidder.clone()),
+            gross: winning_bid.map(|bid| bid.amount).unwrap_or_default(),
+            fee: Uint128::zero(),
+            net: Uint128::zero(),
+            settled_at: closed_at,
+            auction,
+        }),
+        AuctionStatus::Ended | AuctionStatus::Cancelled => {
+            Some(CompletedAuction::unsold(auction, closed_at))
+        }
+        AuctionStatus::Active | AuctionStatus::Sold => None,
+    }
+}
+
+// Rewrite every deployment-package auction in the phoenix-escrow layout,
+// archiving the closed ones. Returns the number of auctions converted.
+pub fn migrate_legacy_auctions(storage: &mut dyn Storage, denom: &str) -> StdResult<u64> {
+    let legacy = LEGACY_AUCTIONS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let count = legacy.len() as u64;
+    for (id, legacy_auction) in legacy {
+        let (auction, legacy_bid) = legacy_auction.into_auction(denom);
+        if let Some(bid) = &legacy_bid {
+            BIDS.save(storage, (id, 0), bid)?;
+        }
+        // Drop the old record first so the indexed save does not try to load it
+        LEGACY_AUCTIONS.remove(storage, id);
+        match closed_record(auction.clone(), legacy_bid.as_ref()) {
+            Some(completed) => COMPLETED_AUCTIONS.save(storage, id, &completed)?,
+            None => AUCTIONS.save(storage, id, &auction)?,
+        }
+    }
+    Ok(count)
+}
+
+// Up to 1.2.x closed auctions stayed in AUCTIONS. Returns the number archived.
+pub fn archive_closed_auctions(storage: &mut dyn Storage) -> StdResult<u64> {
+    let auctions = AUCTIONS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut archived = 0u64;
+    for (id, auction) in auctions {
+        let winning_bid = auction.highest_bid.clone();
+        if let Some(completed) = closed_record(auction, winning_bid.as_ref()) {
+            archive_auction(storage, id, &completed)?;
+            archived += 1;
+        }
+    }
+    Ok(archived)
+}
+
+// Up to 1.1.x the bid history was stored inline on each auction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct InlineBids {
+    #[serde(default)]
+    bids: Vec<Bid>,
+}
+
+const INLINE_BIDS: Map<u64, InlineBids> = Map::new("auctions");
+
+// Move inline bid histories into BIDS, indexing bidders and sellers on the way.
+// Returns the number of bids moved.
+pub fn migrate_inline_bids(storage: &mut dyn Storage) -> StdResult<u64> {
+    let inline = INLINE_BIDS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut moved = 0u64;
+    for (id, InlineBids { bids }) in inline {
+        // Loading drops the old field, saving writes the new layout
+        let mut auction = AUCTIONS.load(storage, id)?;
+        for (sequence, bid) in bids.iter().enumerate() {
+            BIDS.save(storage, (id, sequence as u64), bid)?;
+        }
+        auction.bid_count = bids.len() as u64;
+        AUCTIONS.save(storage, id, &auction)?;
+        moved += auction.bid_count;
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn test_migrate_legacy_auctions() {
+        let mut storage = MockStorage::new();
+        let legacy = LegacyAuction {
+            id: 0,
+            item_id: "gold-bar-1".to_string(),
+            description: "1oz gold bar".to_string(),
+            metal_type: "Gold".to_string(),
+            product_form: "Bar".to_string(),
+            weight: 31,
+            starting_price: Uint128::new(1000),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::new(2000)),
+            highest_bid: Some(Uint128::new(1500)),
+            highest_bidder: Some(Addr::unchecked("bidder")),
+            seller: Addr::unchecked("seller"),
+            status: LegacyAuctionStatus::Active,
+            end_time: Timestamp::from_seconds(86_400),
+            created_at: Timestamp::from_seconds(0),
+        };
+        LEGACY_AUCTIONS.save(&mut storage, 0, &legacy).unwrap();
+        let sold = LegacyAuction {
+            id: 1,
+            status: LegacyAuctionStatus::Completed,
+            ..legacy
+        };
+        LEGACY_AUCTIONS.save(&mut storage, 1, &sold).unwrap();
+
+        assert_eq!(migrate_legacy_auctions(&mut storage, "urlusd").unwrap(), 2);
+
+        let auction = AUCTIONS.load(&storage, 0).unwrap();
+        assert_eq!(auction.creator, Addr::unchecked("seller"));
+        assert_eq!(auction.ends_at, 86_400);
+        assert_eq!(auction.starting_price, Uint128::new(1500));
+        assert_eq!(auction.highest_bid, None);
+        assert_eq!(auction.bid_count, 1);
+        assert_eq!(BIDS.load(&storage, (0, 0)).unwrap().amount, Uint128::new(1500));
+        assert_eq!(auction.status, AuctionStatus::Active);
+
+        // The closed one goes straight to the archive
+        assert!(!AUCTIONS.has(&storage, 1));
+        let completed = COMPLETED_AUCTIONS.load(&storage, 1).unwrap();
+        assert_eq!(completed.auction.status, AuctionStatus::Completed);
+        assert_eq!(completed.winner, Some(Addr::unchecked("bidder")));
+        assert_eq!(completed.gross, Uint128::new(1500));
+        assert_eq!(completed.settled_at, 86_400);
+    }
+
+    #[test]
+    fn test_migrate_inline_bids() {
+        let mut storage = MockStorage::new();
+        // A 1.1.x record with its history inline and no bid_count
+        let record = br#"{"creator":"seller","item_id":"gold-bar-1","denom":"urlusd",
+            "starting_price":"1000","reserve_price":null,"buy_now_price":null,"ends_at":86400,
+            "bids":[{"bidder":"alice","amount":"1100","fee":"0","timestamp":0},
+                    {"bidder":"bob","amount":"1200","fee":"0","timestamp":1}],
+            "highest_bid":{"bidder":"bob","amount":"1200","fee":"0","timestamp":1},
+            "status":"Active","created_at":0}"#;
+        storage.set(&AUCTIONS.key(0), record);
+
+        assert_eq!(migrate_inline_bids(&mut storage).unwrap(), 2);
+        assert_eq!(AUCTIONS.load(&storage, 0).unwrap().bid_count, 2);
+        assert_eq!(BIDS.load(&storage, (0, 1)).unwrap().bidder, Addr::unchecked("bob"));
+    }
+}
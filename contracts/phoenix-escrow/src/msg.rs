@@ -1,13 +1,58 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{
+    AuctionStatus, Ballot, Bid, FeeSchedule, Feedback, FeeSplit, GovConfig, PauseScope, PauseState, PendingFeeSchedule, Proposal,
+    Reputation, ReputationRequirement, RewardEpoch, RewardsConfig, Role, VoteOption,
+};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: String,
-    pub fee_percentage: u64,
-    pub fee_address: String,
+    pub fee_schedule: FeeSchedule,
+    pub fee_timelock: Option<u64>,
+    pub fee_recipients: Vec<FeeRecipientMsg>,
+    pub dust_recipient: String,
     pub require_kyc: Option<bool>,  // Simple KYC flag
+    // Denoms auctions may be priced in (defaults to any)
+    pub accepted_denoms: Option<Vec<String>>,
+    // Insurance pool activation (defaults to 50,000 RLUSD)
+    pub insurance_developer: String,
+    pub insurance_denom: String,
+    pub insurance_activation_threshold: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMsg {
+    // Code upgrade of an existing phoenix-escrow instance
+    Upgrade {},
+    // Upgrade a deployment-package instance: supplies the config it never had
+    // and the denom its auctions were priced in
+    FromLegacy {
+        config: Box<InstantiateMsg>,
+        denom: String,
+    },
+}
+
+// Sent by the chain, e.g. a begin/end-block clock module, never by accounts.
+// Each call ends at most MAX_FINALIZE_LIMIT expired auctions.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    EndBlock {},
+    FinalizeExpired {
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipientMsg {
+    pub address: String,
+    pub weight_bps: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -15,26 +60,130 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     CreateAuction {
         item_id: String,
+        denom: String,
         starting_price: Uint128,
         reserve_price: Option<Uint128>,
         buy_now_price: Option<Uint128>,
         duration_hours: u64,
+        buyer_requirement: Option<ReputationRequirement>,
     },
+    // Funds cover the bid plus the buyer fee, and the 2% premium if `insure: true`
     PlaceBid {
         auction_id: u64,
+        insure: Option<bool>,
     },
     BuyNow {
         auction_id: u64,
+        insure: Option<bool>,
     },
     EndAuction {
         auction_id: u64,
     },
+    // Ends up to `limit` expired auctions, soonest first. Anyone may call it.
+    FinalizeExpired {
+        limit: Option<u32>,
+    },
     CancelAuction {
         auction_id: u64,
     },
     ReleaseFunds {
         auction_id: u64,
     },
+    // Follow an open auction; the notifier looks watchers up via `Watchers`
+    Watch {
+        auction_id: u64,
+    },
+    Unwatch {
+        auction_id: u64,
+    },
+    
+    WithdrawFees {
+        denom: String,
+    },
+    // Unset fields are left unchanged
+    UpdateConfig {
+        fee_schedule: Option<FeeSchedule>,
+        fee_timelock: Option<u64>,
+        require_kyc: Option<bool>,
+        insurance_developer: Option<String>,
+        accepted_denoms: Option<Vec<String>>,
+    },
+    // Weights must sum to 10000
+    UpdateFeeSplit {
+        recipients: Vec<FeeRecipientMsg>,
+        dust_recipient: String,
+    },
+    
+    // Counterparties of a completed auction rate each other once, 1 to 5
+    LeaveFeedback {
+        auction_id: u64,
+        rating: u8,
+        comment_hash: String,
+    },
+    
+    // Shipping insurance claims
+    FileClaim {
+        auction_id: u64,
+    },
+    ResolveClaim {
+        auction_id: u64,
+        loss_in_transit: bool,
+    },
+    
+    // Admin and roles
+    ProposeAdmin {
+        address: String,
+    },
+    AcceptAdmin {},
+    RenounceAdmin {},
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    
+    // Circuit breaker, pauser role only
+    Pause {
+        scopes: Vec<PauseScope>,
+    },
+    Unpause {
+        scopes: Vec<PauseScope>,
+    },
+    
+    // Governance, admin configures the voting token
+    ConfigureGovernance {
+        token: String,
+        voting_period: u64,
+        quorum_bps: u64,
+        threshold_bps: u64,
+    },
+    // Only UpdateConfig and UpdateFeeSplit can be proposed
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<ExecuteMsg>,
+    },
+    // Votes are cast by sending voting tokens with a ReceiveMsg::Vote
+    Receive(Cw20ReceiveMsg),
+    Execute {
+        proposal_id: u64,
+    },
+    ReclaimVote {
+        proposal_id: u64,
+    },
+    
+    // Trading rewards, admin configures the minter and emission caps
+    ConfigureRewards {
+        token: String,
+        denom: String,
+        reward_bps: u64,
+        epoch_length: u64,
+        epoch_cap: Uint128,
+    },
+    ClaimRewards {},
     
     // Simple KYC functions
     VerifyUser {
@@ -45,29 +194,129 @@ pub enum ExecuteMsg {
     },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    #[returns(ConfigResponse)]
     Config {},
+    #[returns(AuctionResponse)]
     Auction { id: u64 },
+    // Bids of one auction in the order they were placed
+    #[returns(BidHistoryResponse)]
+    BidHistory {
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Auctions not yet archived; `filter_active` keeps only those taking bids
+    #[returns(ListAuctionsResponse)]
     ListAuctions {
         start_after: Option<u64>,
         limit: Option<u32>,
         filter_active: Option<bool>,
     },
+    #[returns(ListCompletedAuctionsResponse)]
     ListCompletedAuctions {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    // Active auctions with `from <= ends_at <= to`, soonest first. Page with
+    // the (ends_at, id) of the last auction returned.
+    #[returns(ListAuctionsResponse)]
+    EndingBetween {
+        from: u64,
+        to: u64,
+        start_after: Option<(u64, u64)>,
+        limit: Option<u32>,
+    },
+    // Dashboard views, paged by auction id and optionally filtered by status
+    #[returns(ListAuctionsResponse)]
+    AuctionsBySeller {
+        seller: String,
+        status: Option<AuctionStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(ListAuctionsResponse)]
+    AuctionsByBidder {
+        bidder: String,
+        status: Option<AuctionStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(ListAuctionsResponse)]
+    WonAuctions {
+        winner: String,
+        status: Option<AuctionStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(WatchersResponse)]
+    Watchers {
+        auction_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(bool)]
     IsVerified { address: String },
+    #[returns(PoolStateResponse)]
+    PoolState {},
+    #[returns(PolicyResponse)]
+    Policy { auction_id: u64 },
+    #[returns(SimulateFeesResponse)]
+    SimulateFees {
+        auction_id: Option<u64>,
+        amount: Option<Uint128>,
+        address: String,
+    },
+    #[returns(AccruedFeesResponse)]
+    AccruedFees { address: String },
+    #[returns(RolesResponse)]
+    Roles {},
+    #[returns(GovConfigResponse)]
+    GovConfig {},
+    #[returns(ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    #[returns(ListProposalsResponse)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(BallotResponse)]
+    Ballot { proposal_id: u64, voter: String },
+    #[returns(RewardsConfigResponse)]
+    RewardsConfig {},
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { address: String },
+    #[returns(ReputationResponse)]
+    Reputation { address: String },
+    #[returns(RatingsResponse)]
+    Ratings {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Vote {
+        proposal_id: u64,
+        vote: VoteOption,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
-    pub admin: Addr,
-    pub fee_percentage: u64,
-    pub fee_address: Addr,
+    pub admin: Option<Addr>,
+    pub fee_schedule: FeeSchedule,
+    pub fee_timelock: u64,
+    pub pending_fee_schedule: Option<PendingFeeSchedule>,
+    pub fee_split: FeeSplit,
     pub require_kyc: bool,
+    pub accepted_denoms: Vec<String>,
+    pub paused: PauseState,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -76,12 +325,166 @@ pub struct AuctionResponse {
     pub auction: crate::state::Auction,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BidEntry {
+    pub sequence: u64,
+    pub bid: Bid,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BidHistoryResponse {
+    pub auction_id: u64,
+    pub bids: Vec<BidEntry>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ListAuctionsResponse {
     pub auctions: Vec<AuctionResponse>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WatchersResponse {
+    pub auction_id: u64,
+    pub watchers: Vec<Addr>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ListCompletedAuctionsResponse {
-    pub auctions: Vec<AuctionResponse>,
+    pub auctions: Vec<CompletedAuctionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CompletedAuctionResponse {
+    pub id: u64,
+    pub auction: crate::state::Auction,
+    pub winner: Option<Addr>,
+    pub gross: Uint128,
+    pub fee: Uint128,
+    pub net: Uint128,
+    pub settled_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolBalanceResponse {
+    pub denom: String,
+    pub total: Uint128,
+    pub committed: Uint128,
+    pub developer_share: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolStateResponse {
+    pub balances: Vec<PoolBalanceResponse>,
+    pub developer: Addr,
+    pub activation_threshold: Uint128,
+    pub threshold_denom: String,
+    pub insurance_active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PolicyResponse {
+    pub policy: Option<crate::state::Policy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateFeesResponse {
+    pub amount: Uint128,
+    pub buyer_fee_bps: u64,
+    pub buyer_fee: Uint128,
+    pub seller_fee_bps: u64,
+    pub seller_fee: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AccruedFeesResponse {
+    pub fees: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleHolders {
+    pub role: Role,
+    pub holders: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RolesResponse {
+    pub admin: Option<Addr>,
+    pub pending_admin: Option<Addr>,
+    pub roles: Vec<RoleHolders>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GovConfigResponse {
+    pub gov_config: Option<GovConfig>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposal: Proposal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BallotResponse {
+    pub ballot: Option<Ballot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardsConfigResponse {
+    pub rewards_config: Option<RewardsConfig>,
+    pub epoch: Option<RewardEpoch>,
+}
+
+// The fields of ExecuteMsg::CreateAuction, as handed to the auction handler
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuctionParams {
+    pub item_id: String,
+    pub denom: String,
+    pub starting_price: Uint128,
+    pub reserve_price: Option<Uint128>,
+    pub buy_now_price: Option<Uint128>,
+    pub duration_hours: u64,
+    pub buyer_requirement: Option<ReputationRequirement>,
+}
+
+// The fields of ExecuteMsg::UpdateConfig, as handed to the config handler
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ConfigUpdate {
+    pub fee_schedule: Option<FeeSchedule>,
+    pub fee_timelock: Option<u64>,
+    pub require_kyc: Option<bool>,
+    pub insurance_developer: Option<String>,
+    pub accepted_denoms: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    pub points: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReputationResponse {
+    pub address: Addr,
+    pub reputation: Reputation,
+    // Share of sales settled without a loss-in-transit payout, None before the first sale
+    pub delivery_rate_bps: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeedbackEntry {
+    pub auction_id: u64,
+    pub feedback: Feedback,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RatingsResponse {
+    pub address: Addr,
+    pub count: u64,
+    pub average: Option<Decimal>,
+    pub feedback: Vec<FeedbackEntry>,
 }
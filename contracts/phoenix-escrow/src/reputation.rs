@@ -0,0 +1,115 @@
+use cosmwasm_std::{Addr, Deps, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::insurance::BPS_DENOMINATOR;
+use crate::msg::ReputationResponse;
+use crate::state::{Reputation, ReputationRequirement, REPUTATIONS};
+
+// A completed trade counts for both sides. The seller's sale counts as delivered
+// unless it was settled through a loss-in-transit payout.
+pub fn record_trade(
+    storage: &mut dyn Storage,
+    buyer: &Addr,
+    seller: &Addr,
+    amount: Uint128,
+    delivered: bool,
+) -> StdResult<()> {
+    REPUTATIONS.update(storage, buyer, |reputation| -> StdResult<_> {
+        let mut reputation = reputation.unwrap_or_default();
+        reputation.purchases += 1;
+        reputation.volume += amount;
+        Ok(reputation)
+    })?;
+    REPUTATIONS.update(storage, seller, |reputation| -> StdResult<_> {
+        let mut reputation = reputation.unwrap_or_default();
+        reputation.sales += 1;
+        reputation.volume += amount;
+        if delivered {
+            reputation.delivered += 1;
+        } else {
+            reputation.lost_in_transit += 1;
+        }
+        Ok(reputation)
+    })?;
+    Ok(())
+}
+
+pub fn record_dispute_lost(storage: &mut dyn Storage, address: &Addr) -> StdResult<()> {
+    REPUTATIONS.update(storage, address, |reputation| -> StdResult<_> {
+        let mut reputation = reputation.unwrap_or_default();
+        reputation.disputes_lost += 1;
+        Ok(reputation)
+    })?;
+    Ok(())
+}
+
+// Seller-set bar a bidder has to clear to bid on an auction
+pub fn ensure_reputation(
+    storage: &dyn Storage,
+    bidder: &Addr,
+    requirement: &Option<ReputationRequirement>,
+) -> Result<(), ContractError> {
+    let requirement = match requirement {
+        Some(requirement) => requirement,
+        None => return Ok(()),
+    };
+    let reputation = REPUTATIONS.may_load(storage, bidder)?.unwrap_or_default();
+    if reputation.purchases < requirement.min_purchases {
+        return Err(ContractError::ReputationTooLow {});
+    }
+    if let Some(max_disputes_lost) = requirement.max_disputes_lost {
+        if reputation.disputes_lost > max_disputes_lost {
+            return Err(ContractError::ReputationTooLow {});
+        }
+    }
+    Ok(())
+}
+
+pub fn query_reputation(deps: Deps, address: String) -> StdResult<ReputationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let reputation: Reputation = REPUTATIONS.may_load(deps.storage, &address)?.unwrap_or_default();
+
+    let delivery_rate_bps =
+        (reputation.delivered * BPS_DENOMINATOR as u64).checked_div(reputation.sales);
+    Ok(ReputationResponse {
+        address,
+        reputation,
+        delivery_rate_bps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn test_reputation_requirement() {
+        let mut deps = mock_dependencies();
+        let buyer = Addr::unchecked("buyer");
+        let seller = Addr::unchecked("seller");
+        let requirement = Some(ReputationRequirement {
+            min_purchases: 1,
+            max_disputes_lost: Some(0),
+        });
+
+        assert_eq!(
+            ensure_reputation(deps.as_ref().storage, &buyer, &requirement),
+            Err(ContractError::ReputationTooLow {})
+        );
+        record_trade(deps.as_mut().storage, &buyer, &seller, Uint128::new(100), true).unwrap();
+        record_trade(deps.as_mut().storage, &buyer, &seller, Uint128::new(300), false).unwrap();
+        assert!(ensure_reputation(deps.as_ref().storage, &buyer, &requirement).is_ok());
+
+        record_dispute_lost(deps.as_mut().storage, &buyer).unwrap();
+        assert_eq!(
+            ensure_reputation(deps.as_ref().storage, &buyer, &requirement),
+            Err(ContractError::ReputationTooLow {})
+        );
+
+        let seller = query_reputation(deps.as_ref(), "seller".to_string()).unwrap();
+        assert_eq!(seller.reputation.sales, 2);
+        assert_eq!(seller.reputation.volume, Uint128::new(400));
+        assert_eq!(seller.delivery_rate_bps, Some(5_000));
+    }
+}
@@ -0,0 +1,208 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::error::ContractError;
+use crate::insurance::BPS_DENOMINATOR;
+use crate::msg::{PendingRewardsResponse, RewardsConfigResponse};
+use crate::roles::ensure_admin;
+use crate::state::{RewardEpoch, RewardsConfig, REWARDS_CONFIG, REWARD_EPOCH, REWARD_POINTS};
+
+// The contract has to be a minter on the reward token. Configuration is
+// admin only, so once governance holds the admin role it sets the caps.
+pub fn execute_configure_rewards(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: String,
+    denom: String,
+    reward_bps: u64,
+    epoch_length: u64,
+    epoch_cap: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    if epoch_length == 0 || reward_bps > BPS_DENOMINATOR as u64 {
+        return Err(ContractError::InvalidConfig {
+            reason: "invalid rewards parameters".to_string(),
+        });
+    }
+
+    let rewards_config = RewardsConfig {
+        token: deps.api.addr_validate(&token)?,
+        denom,
+        reward_bps,
+        epoch_length,
+        epoch_cap,
+    };
+    REWARDS_CONFIG.save(deps.storage, &rewards_config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "configure_rewards")
+        .add_attribute("token", rewards_config.token)
+        .add_attribute("denom", rewards_config.denom)
+        .add_attribute("reward_bps", reward_bps.to_string())
+        .add_attribute("epoch_length", epoch_length.to_string())
+        .add_attribute("epoch_cap", epoch_cap))
+}
+
+// Settlement hook: both sides earn points on volume in the reward denom.
+// Returns the points credited to each party.
+pub fn record_rewards(
+    storage: &mut dyn Storage,
+    buyer: &Addr,
+    seller: &Addr,
+    denom: &str,
+    volume: Uint128,
+) -> StdResult<Uint128> {
+    let rewards_config = match REWARDS_CONFIG.may_load(storage)? {
+        Some(rewards_config) if rewards_config.denom == denom => rewards_config,
+        _ => return Ok(Uint128::zero()),
+    };
+
+    let points = volume.multiply_ratio(rewards_config.reward_bps, BPS_DENOMINATOR);
+    if points.is_zero() {
+        return Ok(points);
+    }
+    for address in [buyer, seller] {
+        REWARD_POINTS.update(storage, address, |pending| -> StdResult<_> {
+            Ok(pending.unwrap_or_default() + points)
+        })?;
+    }
+    Ok(points)
+}
+
+// Epoch in force at `now`; a fresh one starts once the last has run out
+fn current_epoch(
+    storage: &dyn Storage,
+    rewards_config: &RewardsConfig,
+    now: u64,
+) -> StdResult<RewardEpoch> {
+    match REWARD_EPOCH.may_load(storage)? {
+        Some(epoch) if now < epoch.started_at + rewards_config.epoch_length => Ok(epoch),
+        _ => Ok(RewardEpoch {
+            started_at: now,
+            minted: Uint128::zero(),
+        }),
+    }
+}
+
+// Mint pending points 1:1 up to what is left of this epoch's cap;
+// the remainder stays claimable in later epochs
+pub fn execute_claim_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let rewards_config = REWARDS_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::RewardsNotConfigured {})?;
+    let pending = REWARD_POINTS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if pending.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let mut epoch = current_epoch(deps.storage, &rewards_config, env.block.time.seconds())?;
+    let claimed = pending.min(rewards_config.epoch_cap.saturating_sub(epoch.minted));
+    if claimed.is_zero() {
+        return Err(ContractError::EpochCapReached {});
+    }
+
+    epoch.minted += claimed;
+    REWARD_EPOCH.save(deps.storage, &epoch)?;
+    REWARD_POINTS.save(deps.storage, &info.sender, &(pending - claimed))?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: rewards_config.token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                recipient: info.sender.to_string(),
+                amount: claimed,
+            })?,
+            funds: vec![],
+        })
+        .add_attribute("action", "claim_rewards")
+        .add_attribute("recipient", info.sender)
+        .add_attribute("amount", claimed)
+        .add_attribute("remaining", pending - claimed))
+}
+
+pub fn query_rewards_config(deps: Deps, env: Env) -> StdResult<RewardsConfigResponse> {
+    let rewards_config = REWARDS_CONFIG.may_load(deps.storage)?;
+    let epoch = match &rewards_config {
+        Some(rewards_config) => Some(current_epoch(
+            deps.storage,
+            rewards_config,
+            env.block.time.seconds(),
+        )?),
+        None => None,
+    };
+    Ok(RewardsConfigResponse {
+        rewards_config,
+        epoch,
+    })
+}
+
+pub fn query_pending_rewards(deps: Deps, address: String) -> StdResult<PendingRewardsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let points = REWARD_POINTS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    Ok(PendingRewardsResponse { points })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    #[test]
+    fn test_claim_respects_epoch_cap() {
+        let mut deps = mock_dependencies();
+        REWARDS_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &RewardsConfig {
+                    token: Addr::unchecked("phnx"),
+                    denom: "urlusd".to_string(),
+                    reward_bps: 100,
+                    epoch_length: 86_400,
+                    epoch_cap: Uint128::new(15),
+                },
+            )
+            .unwrap();
+
+        // 1% of 1000 to each side; other denoms earn nothing
+        let buyer = Addr::unchecked("buyer");
+        let seller = Addr::unchecked("seller");
+        let points =
+            record_rewards(deps.as_mut().storage, &buyer, &seller, "urlusd", Uint128::new(1_000))
+                .unwrap();
+        assert_eq!(points, Uint128::new(10));
+        let points =
+            record_rewards(deps.as_mut().storage, &buyer, &seller, "uxrp", Uint128::new(1_000))
+                .unwrap();
+        assert!(points.is_zero());
+
+        let mut env = mock_env();
+        execute_claim_rewards(deps.as_mut(), env.clone(), mock_info("buyer", &[])).unwrap();
+        let res = execute_claim_rewards(deps.as_mut(), env.clone(), mock_info("seller", &[])).unwrap();
+        assert_eq!(res.attributes[2].value, "5");
+        assert_eq!(res.attributes[3].value, "5");
+        assert_eq!(
+            execute_claim_rewards(deps.as_mut(), env.clone(), mock_info("seller", &[])),
+            Err(ContractError::EpochCapReached {})
+        );
+
+        // The rest becomes claimable in the next epoch
+        env.block.time = env.block.time.plus_seconds(86_400);
+        let res = execute_claim_rewards(deps.as_mut(), env, mock_info("seller", &[])).unwrap();
+        assert_eq!(res.attributes[2].value, "5");
+        assert_eq!(
+            query_pending_rewards(deps.as_ref(), "seller".to_string()).unwrap().points,
+            Uint128::zero()
+        );
+    }
+}
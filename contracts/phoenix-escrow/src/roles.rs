@@ -0,0 +1,187 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, MessageInfo, Order, Response, StdResult, Storage};
+
+use crate::error::ContractError;
+use crate::events::{
+    AdminAccepted, AdminProposed, AdminRenounced, PhoenixEvent, RoleGranted, RoleRevoked,
+};
+use crate::msg::{RoleHolders, RolesResponse};
+use crate::state::{Role, CONFIG, PENDING_ADMIN, ROLES};
+
+pub const ALL_ROLES: [Role; 4] = [
+    Role::FeeManager,
+    Role::KycOfficer,
+    Role::Arbitrator,
+    Role::Pauser,
+];
+
+pub fn has_role(storage: &dyn Storage, address: &Addr, role: Role) -> bool {
+    ROLES.has(storage, (role.as_str(), address))
+}
+
+pub fn ensure_role(storage: &dyn Storage, address: &Addr, role: Role) -> Result<(), ContractError> {
+    if !has_role(storage, address, role) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn ensure_admin(storage: &dyn Storage, address: &Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    if config.admin.as_ref() != Some(address) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+// An outgoing admin keeps nothing: every role it held goes with the admin
+// rights. Returns a revocation event per role it held.
+fn revoke_all_roles(storage: &mut dyn Storage, address: &Addr) -> Vec<RoleRevoked> {
+    let mut revoked = vec![];
+    for role in ALL_ROLES {
+        if has_role(storage, address, role) {
+            ROLES.remove(storage, (role.as_str(), address));
+            revoked.push(RoleRevoked {
+                role,
+                address: address.clone(),
+            });
+        }
+    }
+    revoked
+}
+
+// Step one: the current admin nominates a successor
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    let proposed = deps.api.addr_validate(&address)?;
+    PENDING_ADMIN.save(deps.storage, &proposed)?;
+
+    let event = AdminProposed {
+        admin: info.sender,
+        proposed: proposed.clone(),
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_attribute("action", "propose_admin")
+        .add_attribute("proposed_admin", proposed))
+}
+
+// Step two: the nominee takes over
+pub fn execute_accept_admin(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    if info.sender != pending {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous = config.admin.replace(pending.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+    let revoked = match previous.as_ref().filter(|previous| **previous != pending) {
+        Some(previous) => revoke_all_roles(deps.storage, previous),
+        None => vec![],
+    };
+
+    let event = AdminAccepted {
+        previous_admin: previous.clone(),
+        admin: pending.clone(),
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_events(revoked.iter().map(PhoenixEvent::to_event))
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous.map(String::from).unwrap_or_default())
+        .add_attribute("admin", pending))
+}
+
+// Gives up admin rights and the admin's own roles for good. Other role
+// holders keep theirs.
+pub fn execute_renounce_admin(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.admin = None;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+    let revoked = revoke_all_roles(deps.storage, &info.sender);
+
+    let event = AdminRenounced {
+        previous_admin: info.sender.clone(),
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_events(revoked.iter().map(PhoenixEvent::to_event))
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("previous_admin", info.sender))
+}
+
+pub fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.save(deps.storage, (role.as_str(), &address), &true)?;
+
+    let event = RoleGranted {
+        role,
+        address: address.clone(),
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    let event = RoleRevoked {
+        role,
+        address: address.clone(),
+    };
+    Ok(Response::new()
+        .add_event(event.to_event())
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let roles = ALL_ROLES
+        .iter()
+        .map(|role| {
+            let holders = ROLES
+                .prefix(role.as_str())
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            Ok(RoleHolders {
+                role: *role,
+                holders,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(RolesResponse {
+        admin: config.admin,
+        pending_admin: PENDING_ADMIN.may_load(deps.storage)?,
+        roles,
+    })
+}
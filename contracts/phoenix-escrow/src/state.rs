@@ -1,35 +1,165 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, StdError, StdResult, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
 
+use crate::msg::ExecuteMsg;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
-    pub admin: Addr,
-    pub fee_percentage: u64,
-    pub fee_address: Addr,
+    // None once the admin has renounced
+    pub admin: Option<Addr>,
+    pub fee_schedule: FeeSchedule,
+    // Delay in seconds before a fee increase takes effect
+    pub fee_timelock: u64,
+    pub fee_split: FeeSplit,
     // Simple KYC toggle
     pub require_kyc: bool,
+    // Denoms auctions may be priced in; empty accepts any
+    #[serde(default)]
+    pub accepted_denoms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    FeeManager,
+    KycOfficer,
+    Arbitrator,
+    Pauser,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::FeeManager => "fee_manager",
+            Role::KycOfficer => "kyc_officer",
+            Role::Arbitrator => "arbitrator",
+            Role::Pauser => "pauser",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = StdError;
+
+    fn from_str(role: &str) -> StdResult<Self> {
+        match role {
+            "fee_manager" => Ok(Role::FeeManager),
+            "kyc_officer" => Ok(Role::KycOfficer),
+            "arbitrator" => Ok(Role::Arbitrator),
+            "pauser" => Ok(Role::Pauser),
+            _ => Err(StdError::generic_err(format!("unknown role {}", role))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseScope {
+    NewAuctions,
+    Bidding,
+    Settlement,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PauseState {
+    pub new_auctions: bool,
+    pub bidding: bool,
+    pub settlement: bool,
+}
+
+// Fees in basis points (110 = 1.1%)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeSchedule {
+    pub buyer_fee_bps: u64,
+    pub seller_fee_bps: u64,
+    // Ascending by min_volume; the highest tier reached applies
+    pub tiers: Vec<VolumeTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingFeeSchedule {
+    pub fee_schedule: FeeSchedule,
+    pub effective_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VolumeTier {
+    pub min_volume: Uint128,
+    // Reduction of the fee rate, 2500 = 25% off
+    pub discount_bps: u64,
+}
+
+// Weighted fee recipients; the contract's own address stands for the insurance pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeSplit {
+    pub recipients: Vec<FeeRecipient>,
+    // Receives the rounding remainder
+    pub dust_recipient: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipient {
+    pub address: Addr,
+    pub weight_bps: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Auction {
     pub creator: Addr,
     pub item_id: String,
+    pub denom: String,
     pub starting_price: Uint128,
     pub reserve_price: Option<Uint128>,
     pub buy_now_price: Option<Uint128>,
     pub ends_at: u64,
-    pub bids: Vec<Bid>,
+    // Bids themselves live in BIDS under (auction id, sequence)
+    #[serde(default)]
+    pub bid_count: u64,
     pub highest_bid: Option<Bid>,
     pub status: AuctionStatus,
     pub created_at: u64,
+    // Minimum reputation the seller requires of bidders
+    #[serde(default)]
+    pub buyer_requirement: Option<ReputationRequirement>,
+}
+
+// Final state of a closed auction. Without a winner the amounts are zero.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CompletedAuction {
+    pub auction: Auction,
+    pub winner: Option<Addr>,
+    pub gross: Uint128,
+    // Buyer and seller fee together
+    pub fee: Uint128,
+    // Paid out to the seller
+    pub net: Uint128,
+    pub settled_at: u64,
+}
+
+impl CompletedAuction {
+    // Closed without a sale
+    pub fn unsold(auction: Auction, closed_at: u64) -> Self {
+        CompletedAuction {
+            auction,
+            winner: None,
+            gross: Uint128::zero(),
+            fee: Uint128::zero(),
+            net: Uint128::zero(),
+            settled_at: closed_at,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Bid {
     pub bidder: Addr,
     pub amount: Uint128,
+    // Buyer fee escrowed on top of the bid
+    pub fee: Uint128,
     pub timestamp: u64,
 }
 
@@ -38,14 +168,254 @@ pub enum AuctionStatus {
     Active,
     Ended,
     Sold,
+    Completed,
     Cancelled,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolState {
+    // Holds the 10% ownership stake in the pool
+    pub developer: Addr,
+    pub activation_threshold: Uint128,
+    pub threshold_denom: String,
+    pub insurance_active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PoolBalance {
+    pub total: Uint128,
+    // Coverage reserved by open shipping policies
+    pub committed: Uint128,
+    pub developer_share: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Policy {
+    pub buyer: Addr,
+    pub denom: String,
+    pub coverage: Uint128,
+    pub premium: Uint128,
+    pub status: PolicyStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PolicyStatus {
+    Active,
+    Claimed,
+    Closed,
+    PaidOut,
+}
+
+// Token-holder governance over this contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GovConfig {
+    // CW20 voting token
+    pub token: Addr,
+    // Seconds a proposal stays open for votes
+    pub voting_period: u64,
+    // Share of the token supply that has to vote, in bps
+    pub quorum_bps: u64,
+    // Share of yes among yes + no votes needed to pass, in bps
+    pub threshold_bps: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub title: String,
+    pub description: String,
+    // Executed against this contract with the contract as sender
+    pub msgs: Vec<ExecuteMsg>,
+    pub yes: Uint128,
+    pub no: Uint128,
+    pub abstain: Uint128,
+    pub expires_at: u64,
+    pub status: ProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Open,
+    Rejected,
+    Executed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteOption {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ballot {
+    pub vote: VoteOption,
+    // Tokens locked with the vote
+    pub weight: Uint128,
+    pub reclaimed: bool,
+}
+
+// PHNX trading rewards, minted from a CW20 this contract is minter of
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardsConfig {
+    pub token: Addr,
+    // Only settled volume in this denom earns points
+    pub denom: String,
+    // Points per unit of volume for each side, in bps
+    pub reward_bps: u64,
+    // Seconds per emission epoch
+    pub epoch_length: u64,
+    // Most tokens minted across all claims within one epoch
+    pub epoch_cap: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardEpoch {
+    pub started_at: u64,
+    pub minted: Uint128,
+}
+
+// Trade history per address, updated at settlement and claim resolution
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Reputation {
+    pub sales: u64,
+    pub purchases: u64,
+    // Settled volume on either side
+    pub volume: Uint128,
+    pub disputes_lost: u64,
+    // Sales settled on the buyer's release, and those settled through a
+    // loss-in-transit payout instead
+    pub delivered: u64,
+    pub lost_in_transit: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReputationRequirement {
+    pub min_purchases: u64,
+    pub max_disputes_lost: Option<u64>,
+}
+
+// Rating left by one counterparty of a completed auction for the other
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Feedback {
+    pub from: Addr,
+    // 1 to 5
+    pub rating: u8,
+    // Hash of the off-chain comment
+    pub comment_hash: String,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct RatingSummary {
+    pub count: u64,
+    // Sum of all ratings received
+    pub total: u64,
+}
+
 // Storage
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
+pub const PENDING_ADMIN: Item<Addr> = Item::new("pending_admin");
+// Role holders, keyed by (role, address)
+pub const ROLES: Map<(&str, &Addr), bool> = Map::new("roles");
+pub const PAUSE_STATE: Item<PauseState> = Item::new("pause_state");
+// Fee increase announced on-chain, applied once effective_at passes
+pub const PENDING_FEE_SCHEDULE: Item<PendingFeeSchedule> = Item::new("pending_fee_schedule");
+pub struct AuctionIndexes<'a> {
+    pub seller: MultiIndex<'a, Addr, Auction, u64>,
+}
+
+impl<'a> IndexList<Auction> for AuctionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Auction>> + '_> {
+        let v: Vec<&dyn Index<Auction>> = vec![&self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const AUCTIONS: IndexedMap<u64, Auction, AuctionIndexes> = IndexedMap::new(
+    "auctions",
+    AuctionIndexes {
+        seller: MultiIndex::new(|_, auction| auction.creator.clone(), "auctions", "auctions__seller"),
+    },
+);
 pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
-pub const COMPLETED_AUCTIONS: Map<u64, Auction> = Map::new("completed_auctions");
+// Active auctions keyed by (ends_at, auction id), soonest first
+pub const EXPIRY_QUEUE: Map<(u64, u64), bool> = Map::new("expiry_queue");
+pub struct BidIndexes<'a> {
+    pub bidder: MultiIndex<'a, Addr, Bid, (u64, u64)>,
+}
+
+impl<'a> IndexList<Bid> for BidIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bid>> + '_> {
+        let v: Vec<&dyn Index<Bid>> = vec![&self.bidder];
+        Box::new(v.into_iter())
+    }
+}
+
+// Keyed by (auction id, sequence)
+pub const BIDS: IndexedMap<(u64, u64), Bid, BidIndexes> = IndexedMap::new(
+    "bids",
+    BidIndexes {
+        bidder: MultiIndex::new(|_, bid| bid.bidder.clone(), "bids", "bids__bidder"),
+    },
+);
+pub struct CompletedAuctionIndexes<'a> {
+    pub seller: MultiIndex<'a, Addr, CompletedAuction, u64>,
+}
+
+impl<'a> IndexList<CompletedAuction> for CompletedAuctionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<CompletedAuction>> + '_> {
+        let v: Vec<&dyn Index<CompletedAuction>> = vec![&self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+// Settled, cancelled and unsold auctions, moved out of AUCTIONS
+pub const COMPLETED_AUCTIONS: IndexedMap<u64, CompletedAuction, CompletedAuctionIndexes> =
+    IndexedMap::new(
+        "completed_auctions",
+        CompletedAuctionIndexes {
+            seller: MultiIndex::new(
+                |_, completed| completed.auction.creator.clone(),
+                "completed_auctions",
+                "completed_auctions__seller",
+            ),
+        },
+    );
+
+// Fees owed per (recipient, denom), pulled via WithdrawFees
+pub const ACCRUED_FEES: Map<(&Addr, &str), Uint128> = Map::new("accrued_fees");
+
+// Cumulative settled volume per address, drives fee tiers
+pub const VOLUMES: Map<&Addr, Uint128> = Map::new("volumes");
+
+// Insurance pool: platform fees accounted per denom
+pub const POOL_STATE: Item<PoolState> = Item::new("pool_state");
+pub const POOL_BALANCES: Map<&str, PoolBalance> = Map::new("pool_balances");
+// Shipping insurance policy per auction
+pub const POLICIES: Map<u64, Policy> = Map::new("policies");
+
+// Addresses following an auction, keyed by (auction id, watcher)
+pub const WATCHERS: Map<(u64, &Addr), bool> = Map::new("watchers");
 
 // Simple KYC: just a map of verified addresses
 pub const KYC_VERIFIED: Map<&Addr, bool> = Map::new("kyc_verified");
+
+pub const REPUTATIONS: Map<&Addr, Reputation> = Map::new("reputations");
+// Feedback received, keyed by (rated address, auction id)
+pub const FEEDBACK: Map<(&Addr, u64), Feedback> = Map::new("feedback");
+pub const RATINGS: Map<&Addr, RatingSummary> = Map::new("ratings");
+
+// Trading rewards
+pub const REWARDS_CONFIG: Item<RewardsConfig> = Item::new("rewards_config");
+pub const REWARD_EPOCH: Item<RewardEpoch> = Item::new("reward_epoch");
+// Unclaimed points per address
+pub const REWARD_POINTS: Map<&Addr, Uint128> = Map::new("reward_points");
+
+// Governance
+pub const GOV_CONFIG: Item<GovConfig> = Item::new("gov_config");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const BALLOTS: Map<(u64, &Addr), Ballot> = Map::new("ballots");
@@ -0,0 +1,143 @@
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::archive::load_any_auction;
+use crate::msg::{AuctionResponse, ListAuctionsResponse};
+use crate::state::{Auction, AuctionStatus, AUCTIONS, BIDS, COMPLETED_AUCTIONS};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+fn matches(auction: &Auction, status: &Option<AuctionStatus>) -> bool {
+    status.is_none() || status.as_ref() == Some(&auction.status)
+}
+
+// Auctions not yet archived, by id. With `filter_active` only those still
+// taking bids are returned; closed ones are listed by ListCompletedAuctions.
+pub fn query_list_auctions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    filter_active: Option<bool>,
+) -> StdResult<ListAuctionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let status = filter_active.unwrap_or(false).then_some(AuctionStatus::Active);
+
+    let auctions = AUCTIONS
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, auction)| matches(auction, &status)))
+        .take(limit)
+        .map(|item| item.map(|(id, auction)| AuctionResponse { id, auction }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListAuctionsResponse { auctions })
+}
+
+// Auctions listed by `seller`, by id. Open ones come from AUCTIONS and closed
+// ones from the archive, merged in id order.
+pub fn query_auctions_by_seller(
+    deps: Deps,
+    seller: String,
+    status: Option<AuctionStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListAuctionsResponse> {
+    let seller = deps.api.addr_validate(&seller)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let open = AUCTIONS
+        .idx
+        .seller
+        .prefix(seller.clone())
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, auction)| matches(auction, &status)))
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let closed = COMPLETED_AUCTIONS
+        .idx
+        .seller
+        .prefix(seller)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .map(|item| item.map(|(id, completed)| (id, completed.auction)))
+        .filter(|item| item.as_ref().map_or(true, |(_, auction)| matches(auction, &status)))
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut auctions: Vec<AuctionResponse> = open
+        .into_iter()
+        .chain(closed)
+        .map(|(id, auction)| AuctionResponse { id, auction })
+        .collect();
+    auctions.sort_by_key(|auction| auction.id);
+    auctions.truncate(limit);
+    Ok(ListAuctionsResponse { auctions })
+}
+
+// Auctions `bidder` has placed at least one bid on, by id
+pub fn query_auctions_by_bidder(
+    deps: Deps,
+    bidder: String,
+    status: Option<AuctionStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListAuctionsResponse> {
+    let bidder = deps.api.addr_validate(&bidder)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let auctions = collect_bid_on(deps, &bidder, start_after, limit, |auction| {
+        matches(auction, &status)
+    })?;
+    Ok(ListAuctionsResponse { auctions })
+}
+
+// Auctions `winner` won. Without a status filter both Sold (awaiting
+// shipment) and Completed are returned.
+pub fn query_won_auctions(
+    deps: Deps,
+    winner: String,
+    status: Option<AuctionStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListAuctionsResponse> {
+    let winner = deps.api.addr_validate(&winner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let auctions = collect_bid_on(deps, &winner, start_after, limit, |auction| {
+        let won = auction.highest_bid.as_ref().map(|bid| &bid.bidder) == Some(&winner)
+            && matches!(auction.status, AuctionStatus::Sold | AuctionStatus::Completed);
+        won && matches(auction, &status)
+    })?;
+    Ok(ListAuctionsResponse { auctions })
+}
+
+// Walk the bidder index in auction id order, visiting each auction once
+fn collect_bid_on(
+    deps: Deps,
+    bidder: &Addr,
+    start_after: Option<u64>,
+    limit: usize,
+    filter: impl Fn(&Auction) -> bool,
+) -> StdResult<Vec<AuctionResponse>> {
+    let start = start_after.map(|id| Bound::exclusive((id, u64::MAX)));
+    let keys = BIDS
+        .idx
+        .bidder
+        .prefix(bidder.clone())
+        .keys(deps.storage, start, None, Order::Ascending);
+
+    let mut auctions = vec![];
+    let mut last_id = None;
+    for key in keys {
+        let (id, _) = key?;
+        if last_id == Some(id) {
+            continue;
+        }
+        last_id = Some(id);
+
+        let auction = load_any_auction(deps.storage, id)?;
+        if filter(&auction) {
+            auctions.push(AuctionResponse { id, auction });
+            if auctions.len() == limit {
+                break;
+            }
+        }
+    }
+    Ok(auctions)
+}
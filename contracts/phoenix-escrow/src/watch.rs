@@ -0,0 +1,55 @@
+use cosmwasm_std::{Deps, DepsMut, MessageInfo, Order, Response, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::WatchersResponse;
+use crate::state::{AUCTIONS, WATCHERS};
+
+pub fn execute_watch(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    if !AUCTIONS.has(deps.storage, auction_id) {
+        return Err(ContractError::AuctionNotFound {});
+    }
+    WATCHERS.save(deps.storage, (auction_id, &info.sender), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "watch")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("watcher", info.sender))
+}
+
+pub fn execute_unwatch(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    WATCHERS.remove(deps.storage, (auction_id, &info.sender));
+
+    Ok(Response::new()
+        .add_attribute("action", "unwatch")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("watcher", info.sender))
+}
+
+pub fn query_watchers(
+    deps: Deps,
+    auction_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<WatchersResponse> {
+    let limit = limit.unwrap_or(10).min(30) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let watchers = WATCHERS
+        .prefix(auction_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(WatchersResponse { auction_id, watchers })
+}
@@ -0,0 +1,196 @@
+// Shared cw-multi-test harness: an escrow whose fees go to a treasury contract,
+// and a few funded accounts
+#![allow(dead_code)]
+
+use cosmwasm_std::{coins, Addr, CosmosMsg, Empty, StdResult, Uint128};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+use phoenix_escrow::helpers::EscrowContract;
+use phoenix_escrow::msg::{AuctionParams, FeeRecipientMsg, InstantiateMsg};
+use phoenix_escrow::state::FeeSchedule;
+
+pub const DENOM: &str = "urlusd";
+pub const HOUR: u64 = 3600;
+pub const INITIAL_BALANCE: u128 = 100_000;
+pub const BUYER_FEE_BPS: u64 = 100;
+pub const SELLER_FEE_BPS: u64 = 200;
+
+pub const SELLER: &str = "seller";
+pub const ALICE: &str = "alice";
+pub const BOB: &str = "bob";
+pub const CAROL: &str = "carol";
+pub const GOVERNANCE: &str = "governance";
+
+fn escrow_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            phoenix_escrow::contract::execute,
+            phoenix_escrow::contract::instantiate,
+            phoenix_escrow::contract::query,
+        )
+        .with_sudo(phoenix_escrow::contract::sudo),
+    )
+}
+
+fn treasury_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        phoenix_treasury::contract::execute,
+        phoenix_treasury::contract::instantiate,
+        phoenix_treasury::contract::query,
+    ))
+}
+
+pub struct Suite {
+    pub app: App,
+    pub escrow: EscrowContract,
+    pub treasury: Addr,
+}
+
+impl Suite {
+    pub fn new() -> Self {
+        let mut app = App::new(|router, _, storage| {
+            for buyer in [ALICE, BOB, CAROL] {
+                router
+                    .bank
+                    .init_balance(storage, &Addr::unchecked(buyer), coins(INITIAL_BALANCE, DENOM))
+                    .unwrap();
+            }
+        });
+
+        // The treasury learns the escrow address once both exist
+        let treasury_code = app.store_code(treasury_contract());
+        let treasury = app
+            .instantiate_contract(
+                treasury_code,
+                Addr::unchecked(GOVERNANCE),
+                &phoenix_treasury::msg::InstantiateMsg {
+                    governance: GOVERNANCE.to_string(),
+                    escrow: None,
+                    approvers: vec![GOVERNANCE.to_string()],
+                    threshold: 1,
+                },
+                &[],
+                "phoenix-treasury",
+                None,
+            )
+            .unwrap();
+
+        let escrow_code = app.store_code(escrow_contract());
+        let escrow = app
+            .instantiate_contract(
+                escrow_code,
+                Addr::unchecked("admin"),
+                &InstantiateMsg {
+                    admin: "admin".to_string(),
+                    fee_schedule: FeeSchedule {
+                        buyer_fee_bps: BUYER_FEE_BPS,
+                        seller_fee_bps: SELLER_FEE_BPS,
+                        tiers: vec![],
+                    },
+                    fee_recipients: vec![FeeRecipientMsg {
+                        address: treasury.to_string(),
+                        weight_bps: 10_000,
+                    }],
+                    dust_recipient: treasury.to_string(),
+                    fee_timelock: None,
+                    require_kyc: None,
+                    accepted_denoms: None,
+                    insurance_developer: "developer".to_string(),
+                    insurance_denom: DENOM.to_string(),
+                    insurance_activation_threshold: None,
+                },
+                &[],
+                "phoenix-escrow",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked(GOVERNANCE),
+            treasury.clone(),
+            &phoenix_treasury::msg::ExecuteMsg::UpdateConfig {
+                governance: None,
+                escrow: Some(escrow.to_string()),
+                approvers: None,
+                threshold: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        Suite {
+            app,
+            escrow: EscrowContract(escrow),
+            treasury,
+        }
+    }
+
+    pub fn create_auction(&mut self, params: AuctionParams) -> u64 {
+        let msg = self.escrow.create_auction(params).unwrap();
+        let res = self.app.execute(Addr::unchecked(SELLER), msg).unwrap();
+        wasm_attribute(&res, "auction_id").parse().unwrap()
+    }
+
+    // Bid `amount`, paying the buyer fee on top through the client
+    pub fn bid(&mut self, bidder: &str, auction_id: u64, amount: u128) -> anyhow::Result<AppResponse> {
+        let msg = self
+            .escrow
+            .place_bid(&self.app.wrap(), auction_id, bidder, Uint128::new(amount), false)?;
+        self.app.execute(Addr::unchecked(bidder), msg)
+    }
+
+    pub fn buy_now(&mut self, buyer: &str, auction_id: u64) -> anyhow::Result<AppResponse> {
+        let msg = self.escrow.buy_now(&self.app.wrap(), auction_id, buyer, false)?;
+        self.app.execute(Addr::unchecked(buyer), msg)
+    }
+
+    // Run a message built by the escrow client
+    pub fn execute(&mut self, sender: &str, msg: StdResult<CosmosMsg>) -> anyhow::Result<AppResponse> {
+        self.app.execute(Addr::unchecked(sender), msg?)
+    }
+
+    pub fn collect_fees(&mut self) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(CAROL),
+            self.treasury.clone(),
+            &phoenix_treasury::msg::ExecuteMsg::CollectFees {
+                denom: DENOM.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn advance(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+
+    pub fn balance(&self, address: impl Into<String>) -> u128 {
+        self.app.wrap().query_balance(address, DENOM).unwrap().amount.u128()
+    }
+
+    pub fn escrow_balance(&self) -> u128 {
+        self.balance(self.escrow.addr())
+    }
+
+    pub fn treasury_balance(&self) -> u128 {
+        self.balance(self.treasury.clone())
+    }
+}
+
+pub fn wasm_attribute(res: &AppResponse, key: &str) -> String {
+    res.events
+        .iter()
+        .filter(|event| event.ty == "wasm")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.clone())
+        .unwrap()
+}
+
+// What a bidder pays for a bid of `amount`
+pub fn with_buyer_fee(amount: u128) -> u128 {
+    amount + amount * BUYER_FEE_BPS as u128 / 10_000
+}
@@ -0,0 +1,258 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Uint128,
+};
+use cw20::{
+    BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse,
+};
+use cw_multi_test::{ContractWrapper, Executor};
+use cw_storage_plus::Map;
+
+use phoenix_escrow::error::ContractError;
+use phoenix_escrow::msg::{ConfigUpdate, ExecuteMsg};
+use phoenix_escrow::state::{FeeSchedule, ProposalStatus, Role, VoteOption};
+
+const VOTING_PERIOD: u64 = 7 * 24 * HOUR;
+
+// Just enough of a CW20 for voting: balances, transfers and sends
+const BALANCES: Map<&Addr, Uint128> = Map::new("balances");
+
+fn move_tokens(deps: DepsMut, from: &Addr, to: &Addr, amount: Uint128) -> StdResult<()> {
+    BALANCES.update(deps.storage, from, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    BALANCES.update(deps.storage, to, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
+
+fn token_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    balances: Vec<Cw20Coin>,
+) -> StdResult<Response> {
+    for coin in balances {
+        BALANCES.save(deps.storage, &Addr::unchecked(coin.address), &coin.amount)?;
+    }
+    Ok(Response::new())
+}
+
+fn token_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        Cw20ExecuteMsg::Transfer { recipient, amount } => {
+            move_tokens(deps, &info.sender, &Addr::unchecked(recipient), amount)?;
+            Ok(Response::new())
+        }
+        Cw20ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => {
+            move_tokens(deps, &info.sender, &Addr::unchecked(&contract), amount)?;
+            let receive = Cw20ReceiveMsg {
+                sender: info.sender.to_string(),
+                amount,
+                msg,
+            };
+            Ok(Response::new().add_message(receive.into_cosmos_msg(contract)?))
+        }
+        _ => Err(StdError::generic_err("unsupported")),
+    }
+}
+
+fn token_query(deps: Deps, _env: Env, msg: Cw20QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw20QueryMsg::Balance { address } => {
+            let balance = BALANCES
+                .may_load(deps.storage, &Addr::unchecked(address))?
+                .unwrap_or_default();
+            to_json_binary(&BalanceResponse { balance })
+        }
+        Cw20QueryMsg::TokenInfo {} => {
+            let total_supply = BALANCES
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, balance)| balance))
+                .sum::<StdResult<Uint128>>()?;
+            to_json_binary(&TokenInfoResponse {
+                name: "Phoenix".to_string(),
+                symbol: "PHNX".to_string(),
+                decimals: 6,
+                total_supply,
+            })
+        }
+        _ => Err(StdError::generic_err("unsupported")),
+    }
+}
+
+fn setup_governance(suite: &mut Suite) -> Addr {
+    let code = suite
+        .app
+        .store_code(Box::new(ContractWrapper::<_, _, _, _, _, _, Empty>::new(
+            token_execute,
+            token_instantiate,
+            token_query,
+        )));
+    let balances = [(ALICE, 600u128), (BOB, 300), (CAROL, 100)]
+        .map(|(address, amount)| Cw20Coin {
+            address: address.to_string(),
+            amount: Uint128::new(amount),
+        })
+        .to_vec();
+    let token = suite
+        .app
+        .instantiate_contract(
+            code,
+            Addr::unchecked(GOVERNANCE),
+            &balances,
+            &[],
+            "phnx",
+            None,
+        )
+        .unwrap();
+
+    // 20% quorum, simple majority; the escrow needs the fee manager role to
+    // apply fee changes on its own behalf
+    let configure =
+        suite
+            .escrow
+            .configure_governance(token.to_string(), VOTING_PERIOD, 2_000, 5_001);
+    suite.execute("admin", configure).unwrap();
+    let grant = suite
+        .escrow
+        .grant_role(Role::FeeManager, suite.escrow.addr());
+    suite.execute("admin", grant).unwrap();
+    token
+}
+
+fn token_balance(suite: &Suite, token: &Addr, address: &str) -> u128 {
+    let res: BalanceResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance.u128()
+}
+
+#[test]
+fn passed_proposal_updates_config() {
+    let mut suite = Suite::new();
+    let token = setup_governance(&mut suite);
+
+    let fee_cut = FeeSchedule {
+        buyer_fee_bps: BUYER_FEE_BPS,
+        seller_fee_bps: 100,
+        tiers: vec![],
+    };
+    let propose = suite.escrow.propose(
+        "Lower seller fee",
+        "Cut the seller fee to 1%",
+        vec![ConfigUpdate::default().fee_schedule(fee_cut).into()],
+    );
+    let res = suite.execute(ALICE, propose).unwrap();
+    let proposal_id: u64 = wasm_attribute(&res, "proposal_id").parse().unwrap();
+
+    // Voting locks the tokens in the escrow
+    let vote = suite.escrow.vote(
+        token.to_string(),
+        Uint128::new(600),
+        proposal_id,
+        VoteOption::Yes,
+    );
+    suite.execute(ALICE, vote).unwrap();
+    let vote = suite.escrow.vote(
+        token.to_string(),
+        Uint128::new(300),
+        proposal_id,
+        VoteOption::No,
+    );
+    suite.execute(BOB, vote).unwrap();
+    let vote = suite.escrow.vote(
+        token.to_string(),
+        Uint128::new(100),
+        proposal_id,
+        VoteOption::Yes,
+    );
+    suite.execute(ALICE, vote).unwrap_err();
+    assert_eq!(token_balance(&suite, &token, ALICE), 0);
+    assert_eq!(
+        token_balance(&suite, &token, suite.escrow.addr().as_str()),
+        900
+    );
+
+    let execute = suite.escrow.execute_proposal(proposal_id);
+    suite.execute(CAROL, execute).unwrap_err();
+    let reclaim = suite.escrow.reclaim_vote(proposal_id);
+    suite.execute(ALICE, reclaim).unwrap_err();
+
+    // Once voting closes anyone can execute; the stored message runs as the escrow
+    suite.advance(VOTING_PERIOD);
+    let execute = suite.escrow.execute_proposal(proposal_id);
+    suite.execute(CAROL, execute).unwrap();
+    let config = suite.escrow.config(&suite.app.wrap()).unwrap();
+    assert_eq!(config.fee_schedule.seller_fee_bps, 100);
+    let proposal = suite
+        .escrow
+        .proposal(&suite.app.wrap(), proposal_id)
+        .unwrap()
+        .proposal;
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    let execute = suite.escrow.execute_proposal(proposal_id);
+    suite.execute(CAROL, execute).unwrap_err();
+
+    // Both sides get their tokens back, once
+    for (voter, weight) in [(ALICE, 600), (BOB, 300)] {
+        let reclaim = suite.escrow.reclaim_vote(proposal_id);
+        suite.execute(voter, reclaim).unwrap();
+        assert_eq!(token_balance(&suite, &token, voter), weight);
+        let reclaim = suite.escrow.reclaim_vote(proposal_id);
+        suite.execute(voter, reclaim).unwrap_err();
+    }
+    assert_eq!(
+        token_balance(&suite, &token, suite.escrow.addr().as_str()),
+        0
+    );
+}
+
+#[test]
+fn proposals_only_change_parameters() {
+    let mut suite = Suite::new();
+    setup_governance(&mut suite);
+
+    let takeovers = [
+        ExecuteMsg::ProposeAdmin {
+            address: ALICE.to_string(),
+        },
+        ExecuteMsg::GrantRole {
+            role: Role::Arbitrator,
+            address: ALICE.to_string(),
+        },
+        ExecuteMsg::WithdrawFees {
+            denom: DENOM.to_string(),
+        },
+    ];
+    for msg in takeovers {
+        // A parameter change alongside does not let the rest through
+        let msgs = vec![ConfigUpdate::default().require_kyc(true).into(), msg];
+        let propose = suite.escrow.propose("Take over", "", msgs);
+        let err = suite.execute(ALICE, propose).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ContractError>(),
+            Some(&ContractError::ProposalMsgNotAllowed {})
+        );
+    }
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::*;
+use phoenix_escrow::msg::{AuctionParams, QueryMsg};
+use phoenix_escrow::state::AuctionStatus;
+
+#[test]
+fn competing_bids_end_and_release() {
+    let mut suite = Suite::new();
+    let auction_id = suite.create_auction(
+        AuctionParams::new("gold-bar", DENOM, 1000u128, 24).reserve_price(1500u128),
+    );
+
+    // Each new high bid refunds the previous one, buyer fee included
+    suite.bid(ALICE, auction_id, 1000).unwrap();
+    suite.bid(BOB, auction_id, 1200).unwrap();
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE);
+    suite.bid(BOB, auction_id, 1200).unwrap_err();
+    suite.bid(ALICE, auction_id, 1600).unwrap();
+    assert_eq!(suite.balance(BOB), INITIAL_BALANCE);
+    assert_eq!(suite.escrow_balance(), with_buyer_fee(1600));
+
+    // Anyone can end it once it has run out; the reserve was met
+    let end = suite.escrow.end_auction(auction_id);
+    suite.execute(CAROL, end).unwrap_err();
+    suite.advance(24 * HOUR);
+    let end = suite.escrow.end_auction(auction_id);
+    suite.execute(CAROL, end).unwrap();
+
+    let release = suite.escrow.release_funds(auction_id);
+    suite.execute(BOB, release).unwrap_err();
+    let release = suite.escrow.release_funds(auction_id);
+    suite.execute(ALICE, release).unwrap();
+
+    // 2% seller fee on 1600 plus the 16 buyer fee, pulled by the treasury
+    let net = 1600 - 32;
+    assert_eq!(suite.balance(SELLER), net);
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE - with_buyer_fee(1600));
+    assert_eq!(suite.escrow_balance(), 48);
+    suite.collect_fees().unwrap();
+    assert_eq!(suite.escrow_balance(), 0);
+    assert_eq!(suite.treasury_balance(), 48);
+
+    let completed = suite
+        .escrow
+        .list_completed_auctions(&suite.app.wrap(), None, None)
+        .unwrap();
+    assert_eq!(completed.auctions[0].net.u128(), net);
+}
+
+#[test]
+fn buy_now_refunds_leading_bid() {
+    let mut suite = Suite::new();
+    let auction_id = suite.create_auction(
+        AuctionParams::new("silver-coin", DENOM, 1000u128, 24).buy_now_price(5000u128),
+    );
+
+    suite.bid(ALICE, auction_id, 1000).unwrap();
+    suite.buy_now(CAROL, auction_id).unwrap();
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE);
+    assert_eq!(suite.balance(CAROL), INITIAL_BALANCE - with_buyer_fee(5000));
+
+    // No more bids once it is sold
+    suite.bid(BOB, auction_id, 6000).unwrap_err();
+
+    let release = suite.escrow.release_funds(auction_id);
+    suite.execute(CAROL, release).unwrap();
+    suite.collect_fees().unwrap();
+
+    assert_eq!(suite.balance(SELLER), 5000 - 100);
+    assert_eq!(suite.treasury_balance(), 100 + 50);
+    assert_eq!(suite.escrow_balance(), 0);
+}
+
+#[test]
+fn unsold_and_cancelled_auctions_return_everything() {
+    let mut suite = Suite::new();
+    let unsold = suite.create_auction(
+        AuctionParams::new("gold-bar", DENOM, 1000u128, 24).reserve_price(5000u128),
+    );
+    let cancelled = suite.create_auction(AuctionParams::new("gold-bar", DENOM, 1000u128, 24));
+
+    // Only the seller can cancel, and only before the first bid
+    let cancel = suite.escrow.cancel_auction(cancelled);
+    suite.execute(BOB, cancel).unwrap_err();
+    let cancel = suite.escrow.cancel_auction(cancelled);
+    suite.execute(SELLER, cancel).unwrap();
+    suite.bid(BOB, cancelled, 1000).unwrap_err();
+
+    suite.bid(ALICE, unsold, 2000).unwrap();
+    let cancel = suite.escrow.cancel_auction(unsold);
+    suite.execute(SELLER, cancel).unwrap_err();
+    suite.advance(24 * HOUR);
+    let end = suite.escrow.end_auction(unsold);
+    suite.execute(CAROL, end).unwrap();
+
+    let auction = suite.escrow.auction(&suite.app.wrap(), unsold).unwrap().auction;
+    assert_eq!(auction.status, AuctionStatus::Ended);
+    let auction: phoenix_escrow::msg::AuctionResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(suite.escrow.addr(), &QueryMsg::Auction { id: cancelled })
+        .unwrap();
+    assert_eq!(auction.auction.status, AuctionStatus::Cancelled);
+
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE);
+    assert_eq!(suite.balance(BOB), INITIAL_BALANCE);
+    assert_eq!(suite.balance(SELLER), 0);
+    assert_eq!(suite.escrow_balance(), 0);
+    suite.collect_fees().unwrap_err();
+    assert_eq!(suite.treasury_balance(), 0);
+}
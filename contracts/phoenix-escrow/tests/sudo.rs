@@ -0,0 +1,78 @@
+mod common;
+
+use common::*;
+use cw_multi_test::AppResponse;
+use phoenix_escrow::msg::{AuctionParams, SudoMsg};
+use phoenix_escrow::state::AuctionStatus;
+
+fn create_auction(suite: &mut Suite, duration_hours: u64) -> u64 {
+    suite.create_auction(
+        AuctionParams::new("gold-bar", DENOM, 1000u128, duration_hours).reserve_price(5000u128),
+    )
+}
+
+fn end_block(suite: &mut Suite, msg: SudoMsg) -> AppResponse {
+    suite.app.wasm_sudo(suite.escrow.addr(), &msg).unwrap()
+}
+
+fn queued(suite: &Suite) -> Vec<u64> {
+    let res = suite
+        .escrow
+        .ending_between(&suite.app.wrap(), 0, u64::MAX, None, Some(30))
+        .unwrap();
+    res.auctions.iter().map(|auction| auction.id).collect()
+}
+
+#[test]
+fn end_block_finalizes_expired_auctions() {
+    let mut suite = Suite::new();
+    create_auction(&mut suite, 24);
+    create_auction(&mut suite, 72);
+    suite.bid(ALICE, 0, 1000).unwrap();
+
+    // Nothing is due before the first auction ends, and the block still succeeds
+    suite.advance(23 * HOUR);
+    let res = end_block(&mut suite, SudoMsg::EndBlock {});
+    assert_eq!(wasm_attribute(&res, "finalized"), "0");
+    assert_eq!(queued(&suite), vec![0, 1]);
+
+    suite.advance(HOUR);
+    let res = end_block(&mut suite, SudoMsg::EndBlock {});
+    assert_eq!(wasm_attribute(&res, "auction_ids"), "0");
+    assert_eq!(queued(&suite), vec![1]);
+
+    // Reserve not met, so the bid came back
+    assert_eq!(suite.balance(ALICE), INITIAL_BALANCE);
+    let auction = suite.escrow.auction(&suite.app.wrap(), 0).unwrap().auction;
+    assert_eq!(auction.status, AuctionStatus::Ended);
+}
+
+#[test]
+fn end_block_work_is_bounded() {
+    let mut suite = Suite::new();
+    for _ in 0..12 {
+        create_auction(&mut suite, 24);
+    }
+    suite.advance(24 * HOUR);
+
+    end_block(&mut suite, SudoMsg::EndBlock {});
+    assert_eq!(queued(&suite), vec![10, 11]);
+
+    end_block(&mut suite, SudoMsg::FinalizeExpired { limit: Some(1) });
+    assert_eq!(queued(&suite), vec![11]);
+    end_block(&mut suite, SudoMsg::EndBlock {});
+    assert!(queued(&suite).is_empty());
+}
+
+#[test]
+fn keeper_sweep_with_nothing_due_succeeds() {
+    let mut suite = Suite::new();
+    let sweep = suite.escrow.finalize_expired(None);
+    let res = suite.execute(CAROL, sweep).unwrap();
+    assert_eq!(wasm_attribute(&res, "finalized"), "0");
+
+    create_auction(&mut suite, 24);
+    let sweep = suite.escrow.finalize_expired(None);
+    suite.execute(CAROL, sweep).unwrap();
+    assert_eq!(queued(&suite), vec![0]);
+}
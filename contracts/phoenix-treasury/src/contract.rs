@@ -0,0 +1,488 @@
+use cosmwasm_std::{
+    coins, entry_point, to_json_binary, Addr, Api, BankMsg, Binary, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, EscrowExecuteMsg, ExecuteMsg, GrantResponse, InstantiateMsg,
+    ListGrantsResponse, MilestoneMsg, QueryMsg,
+};
+use crate::state::{
+    Config, Grant, GrantStatus, Milestone, MilestoneStatus, CONFIG, GRANTS, GRANT_COUNT,
+};
+
+const CONTRACT_NAME: &str = "crates.io:phoenix-treasury";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        governance: deps.api.addr_validate(&msg.governance)?,
+        escrow: msg.escrow.map(|escrow| deps.api.addr_validate(&escrow)).transpose()?,
+        approvers: validate_approvers(deps.api, msg.approvers, msg.threshold)?,
+        threshold: msg.threshold,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    GRANT_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("governance", config.governance))
+}
+
+fn validate_approvers(
+    api: &dyn Api,
+    approvers: Vec<String>,
+    threshold: u32,
+) -> Result<Vec<Addr>, ContractError> {
+    let mut validated: Vec<Addr> = Vec::with_capacity(approvers.len());
+    for approver in approvers {
+        let approver = api.addr_validate(&approver)?;
+        if validated.contains(&approver) {
+            return Err(ContractError::InvalidApprovers {});
+        }
+        validated.push(approver);
+    }
+    if threshold == 0 || threshold as usize > validated.len() {
+        return Err(ContractError::InvalidApprovers {});
+    }
+    Ok(validated)
+}
+
+#[entry_point]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CollectFees { denom } => execute_collect_fees(deps, denom),
+        ExecuteMsg::CreateGrant { grantee, title, denom, milestones } => {
+            execute_create_grant(deps, info, grantee, title, denom, milestones)
+        },
+        ExecuteMsg::CancelGrant { grant_id } => execute_cancel_grant(deps, info, grant_id),
+        ExecuteMsg::UpdateConfig { governance, escrow, approvers, threshold } => {
+            execute_update_config(deps, info, governance, escrow, approvers, threshold)
+        },
+        ExecuteMsg::SubmitMilestone { grant_id, milestone, evidence } => {
+            execute_submit_milestone(deps, info, grant_id, milestone, evidence)
+        },
+        ExecuteMsg::ApproveMilestone { grant_id, milestone } => {
+            execute_approve_milestone(deps, info, grant_id, milestone)
+        },
+        ExecuteMsg::RejectMilestone { grant_id, milestone } => {
+            execute_reject_milestone(deps, info, grant_id, milestone)
+        },
+    }
+}
+
+// Add this contract to the escrow's fee recipients; the escrow accrues the
+// share and this pulls it in
+fn execute_collect_fees(deps: DepsMut, denom: String) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let escrow = config.escrow.ok_or(ContractError::Unauthorized {})?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: escrow.to_string(),
+            msg: to_json_binary(&EscrowExecuteMsg::WithdrawFees { denom: denom.clone() })?,
+            funds: vec![],
+        })
+        .add_attribute("action", "collect_fees")
+        .add_attribute("escrow", escrow)
+        .add_attribute("denom", denom))
+}
+
+fn ensure_governance(config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    if config.governance != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+fn execute_create_grant(
+    deps: DepsMut,
+    info: MessageInfo,
+    grantee: String,
+    title: String,
+    denom: String,
+    milestones: Vec<MilestoneMsg>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_governance(&config, &info.sender)?;
+    if milestones.is_empty() || milestones.iter().any(|m| m.amount.is_zero()) {
+        return Err(ContractError::InvalidGrant {});
+    }
+
+    let grant = Grant {
+        grantee: deps.api.addr_validate(&grantee)?,
+        title,
+        denom,
+        milestones: milestones
+            .into_iter()
+            .map(|m| Milestone {
+                description: m.description,
+                amount: m.amount,
+                evidence: None,
+                approvals: vec![],
+                status: MilestoneStatus::Pending,
+            })
+            .collect(),
+        status: GrantStatus::Active,
+    };
+    let total: Uint128 = grant.milestones.iter().map(|m| m.amount).sum();
+
+    let grant_id = GRANT_COUNT.load(deps.storage)?;
+    GRANTS.save(deps.storage, grant_id, &grant)?;
+    GRANT_COUNT.save(deps.storage, &(grant_id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_grant")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("grantee", grant.grantee)
+        .add_attribute("total", total))
+}
+
+fn execute_cancel_grant(
+    deps: DepsMut,
+    info: MessageInfo,
+    grant_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_governance(&config, &info.sender)?;
+
+    let mut grant = load_active_grant(deps.as_ref(), grant_id)?;
+    grant.status = GrantStatus::Cancelled;
+    GRANTS.save(deps.storage, grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_grant")
+        .add_attribute("grant_id", grant_id.to_string()))
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    governance: Option<String>,
+    escrow: Option<String>,
+    approvers: Option<Vec<String>>,
+    threshold: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    ensure_governance(&config, &info.sender)?;
+
+    if let Some(governance) = governance {
+        config.governance = deps.api.addr_validate(&governance)?;
+    }
+    if let Some(escrow) = escrow {
+        config.escrow = Some(deps.api.addr_validate(&escrow)?);
+    }
+    if approvers.is_some() || threshold.is_some() {
+        let approvers = approvers
+            .unwrap_or_else(|| config.approvers.iter().map(|a| a.to_string()).collect());
+        config.threshold = threshold.unwrap_or(config.threshold);
+        config.approvers = validate_approvers(deps.api, approvers, config.threshold)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("governance", config.governance)
+        .add_attribute("threshold", config.threshold.to_string()))
+}
+
+fn execute_submit_milestone(
+    deps: DepsMut,
+    info: MessageInfo,
+    grant_id: u64,
+    milestone: u32,
+    evidence: String,
+) -> Result<Response, ContractError> {
+    let mut grant = load_active_grant(deps.as_ref(), grant_id)?;
+    if info.sender != grant.grantee {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let entry = milestone_mut(&mut grant, milestone)?;
+    if entry.status == MilestoneStatus::Paid {
+        return Err(ContractError::MilestonePaid {});
+    }
+    // New evidence needs fresh approvals
+    entry.evidence = Some(evidence.clone());
+    entry.approvals.clear();
+    entry.status = MilestoneStatus::Submitted;
+    GRANTS.save(deps.storage, grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_milestone")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("milestone", milestone.to_string())
+        .add_attribute("evidence", evidence))
+}
+
+// The approval that reaches the threshold pays out the tranche
+fn execute_approve_milestone(
+    deps: DepsMut,
+    info: MessageInfo,
+    grant_id: u64,
+    milestone: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.approvers.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut grant = load_active_grant(deps.as_ref(), grant_id)?;
+    let entry = milestone_mut(&mut grant, milestone)?;
+    match entry.status {
+        MilestoneStatus::Paid => return Err(ContractError::MilestonePaid {}),
+        MilestoneStatus::Pending => return Err(ContractError::MilestoneNotSubmitted {}),
+        MilestoneStatus::Submitted => {}
+    }
+    if entry.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {});
+    }
+    entry.approvals.push(info.sender.clone());
+
+    let mut response = Response::new()
+        .add_attribute("action", "approve_milestone")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("milestone", milestone.to_string())
+        .add_attribute("approver", info.sender);
+
+    // Approvals from addresses since removed from the approver set don't count
+    let approvals = entry
+        .approvals
+        .iter()
+        .filter(|approver| config.approvers.contains(approver))
+        .count();
+    if approvals >= config.threshold as usize {
+        entry.status = MilestoneStatus::Paid;
+        let amount = entry.amount;
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: grant.grantee.to_string(),
+                amount: coins(amount.u128(), &grant.denom),
+            })
+            .add_attribute("paid", amount);
+
+        if grant.milestones.iter().all(|m| m.status == MilestoneStatus::Paid) {
+            grant.status = GrantStatus::Completed;
+        }
+    }
+    GRANTS.save(deps.storage, grant_id, &grant)?;
+
+    Ok(response)
+}
+
+fn execute_reject_milestone(
+    deps: DepsMut,
+    info: MessageInfo,
+    grant_id: u64,
+    milestone: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.approvers.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut grant = load_active_grant(deps.as_ref(), grant_id)?;
+    let entry = milestone_mut(&mut grant, milestone)?;
+    if entry.status != MilestoneStatus::Submitted {
+        return Err(ContractError::MilestoneNotSubmitted {});
+    }
+    entry.approvals.clear();
+    entry.status = MilestoneStatus::Pending;
+    GRANTS.save(deps.storage, grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reject_milestone")
+        .add_attribute("grant_id", grant_id.to_string())
+        .add_attribute("milestone", milestone.to_string())
+        .add_attribute("approver", info.sender))
+}
+
+fn load_active_grant(deps: Deps, grant_id: u64) -> Result<Grant, ContractError> {
+    let grant = GRANTS
+        .may_load(deps.storage, grant_id)?
+        .ok_or(ContractError::GrantNotFound {})?;
+    if grant.status != GrantStatus::Active {
+        return Err(ContractError::GrantNotActive {});
+    }
+    Ok(grant)
+}
+
+fn milestone_mut(grant: &mut Grant, milestone: u32) -> Result<&mut Milestone, ContractError> {
+    grant
+        .milestones
+        .get_mut(milestone as usize)
+        .ok_or(ContractError::MilestoneNotFound {})
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&ConfigResponse {
+                governance: config.governance,
+                escrow: config.escrow,
+                approvers: config.approvers,
+                threshold: config.threshold,
+            })
+        }
+        QueryMsg::Grant { id } => {
+            let grant = GRANTS.load(deps.storage, id)?;
+            to_json_binary(&GrantResponse { id, grant })
+        }
+        QueryMsg::ListGrants { start_after, limit } => {
+            let limit = limit.unwrap_or(10).min(30) as usize;
+            let start = start_after.map(Bound::exclusive);
+            let grants = GRANTS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(id, grant)| GrantResponse { id, grant }))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&ListGrantsResponse { grants })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{CosmosMsg, OwnedDeps};
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            governance: "governance".to_string(),
+            escrow: Some("escrow".to_string()),
+            approvers: vec!["alice".to_string(), "bob".to_string(), "carol".to_string()],
+            threshold: 2,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let grant = ExecuteMsg::CreateGrant {
+            grantee: "builder".to_string(),
+            title: "Oracle server".to_string(),
+            denom: "urlusd".to_string(),
+            milestones: vec![
+                MilestoneMsg { description: "Prototype".to_string(), amount: Uint128::new(500) },
+                MilestoneMsg { description: "Mainnet".to_string(), amount: Uint128::new(1_500) },
+            ],
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("builder", &[]), grant.clone());
+        assert_eq!(res, Err(ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info("governance", &[]), grant).unwrap();
+        deps
+    }
+
+    fn run(
+        deps: DepsMut,
+        sender: &str,
+        msg: ExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)
+    }
+
+    #[test]
+    fn test_milestone_payout_needs_threshold() {
+        let mut deps = setup();
+        let approve = ExecuteMsg::ApproveMilestone { grant_id: 0, milestone: 0 };
+
+        let res = run(deps.as_mut(), "alice", approve.clone());
+        assert_eq!(res, Err(ContractError::MilestoneNotSubmitted {}));
+
+        let submit = ExecuteMsg::SubmitMilestone {
+            grant_id: 0,
+            milestone: 0,
+            evidence: "sha256:ab12".to_string(),
+        };
+        run(deps.as_mut(), "builder", submit).unwrap();
+
+        let res = run(deps.as_mut(), "alice", approve.clone()).unwrap();
+        assert!(res.messages.is_empty());
+        let res = run(deps.as_mut(), "alice", approve.clone());
+        assert_eq!(res, Err(ContractError::AlreadyApproved {}));
+
+        let res = run(deps.as_mut(), "bob", approve.clone()).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "builder".to_string(),
+                amount: coins(500, "urlusd"),
+            })
+        );
+        let res = run(deps.as_mut(), "carol", approve);
+        assert_eq!(res, Err(ContractError::MilestonePaid {}));
+
+        let grant = GRANTS.load(deps.as_ref().storage, 0).unwrap();
+        assert_eq!(grant.status, GrantStatus::Active);
+    }
+
+    #[test]
+    fn test_removed_approver_does_not_count() {
+        let mut deps = setup();
+        let submit = ExecuteMsg::SubmitMilestone {
+            grant_id: 0,
+            milestone: 0,
+            evidence: "sha256:ab12".to_string(),
+        };
+        run(deps.as_mut(), "builder", submit).unwrap();
+        let approve = ExecuteMsg::ApproveMilestone { grant_id: 0, milestone: 0 };
+        run(deps.as_mut(), "alice", approve.clone()).unwrap();
+
+        let update = ExecuteMsg::UpdateConfig {
+            governance: None,
+            escrow: None,
+            approvers: Some(vec!["bob".to_string(), "carol".to_string(), "dave".to_string()]),
+            threshold: None,
+        };
+        run(deps.as_mut(), "governance", update).unwrap();
+
+        // Alice's approval predates her removal, so bob alone is not enough
+        let res = run(deps.as_mut(), "bob", approve.clone()).unwrap();
+        assert!(res.messages.is_empty());
+        let res = run(deps.as_mut(), "alice", approve.clone());
+        assert_eq!(res, Err(ContractError::Unauthorized {}));
+
+        let res = run(deps.as_mut(), "carol", approve).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let grant = GRANTS.load(deps.as_ref().storage, 0).unwrap();
+        assert_eq!(grant.milestones[0].status, MilestoneStatus::Paid);
+    }
+
+    #[test]
+    fn test_collect_fees_from_escrow() {
+        let mut deps = setup();
+        let res = run(
+            deps.as_mut(),
+            "anyone",
+            ExecuteMsg::CollectFees { denom: "urlusd".to_string() },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "escrow".to_string(),
+                msg: Binary::from(br#"{"withdraw_fees":{"denom":"urlusd"}}"#.to_vec()),
+                funds: vec![],
+            })
+        );
+    }
+}
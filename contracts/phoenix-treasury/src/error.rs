@@ -0,0 +1,35 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+    
+    #[error("Invalid approvers: threshold must be between 1 and the number of approvers")]
+    InvalidApprovers {},
+    
+    #[error("Grant needs at least one milestone with a positive amount")]
+    InvalidGrant {},
+    
+    #[error("Grant not found")]
+    GrantNotFound {},
+    
+    #[error("Grant is not active")]
+    GrantNotActive {},
+    
+    #[error("Milestone not found")]
+    MilestoneNotFound {},
+    
+    #[error("Milestone already paid")]
+    MilestonePaid {},
+    
+    #[error("Milestone has no evidence submitted")]
+    MilestoneNotSubmitted {},
+    
+    #[error("Already approved")]
+    AlreadyApproved {},
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::Grant;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub governance: String,
+    pub escrow: Option<String>,
+    pub approvers: Vec<String>,
+    pub threshold: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Pull this treasury's accrued fee share out of the escrow, anyone may call
+    CollectFees {
+        denom: String,
+    },
+    
+    // Governance
+    CreateGrant {
+        grantee: String,
+        title: String,
+        denom: String,
+        milestones: Vec<MilestoneMsg>,
+    },
+    CancelGrant {
+        grant_id: u64,
+    },
+    UpdateConfig {
+        governance: Option<String>,
+        escrow: Option<String>,
+        approvers: Option<Vec<String>>,
+        threshold: Option<u32>,
+    },
+    
+    // Grantee
+    SubmitMilestone {
+        grant_id: u64,
+        milestone: u32,
+        evidence: String,
+    },
+    
+    // Approvers
+    ApproveMilestone {
+        grant_id: u64,
+        milestone: u32,
+    },
+    // Sends the milestone back to the grantee and clears its approvals
+    RejectMilestone {
+        grant_id: u64,
+        milestone: u32,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MilestoneMsg {
+    pub description: String,
+    pub amount: Uint128,
+}
+
+// The escrow message this treasury sends, mirrored to avoid linking the escrow crate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowExecuteMsg {
+    WithdrawFees { denom: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Grant { id: u64 },
+    ListGrants {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub governance: Addr,
+    pub escrow: Option<Addr>,
+    pub approvers: Vec<Addr>,
+    pub threshold: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GrantResponse {
+    pub id: u64,
+    pub grant: Grant,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListGrantsResponse {
+    pub grants: Vec<GrantResponse>,
+}
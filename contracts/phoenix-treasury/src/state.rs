@@ -0,0 +1,55 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    // Approves and cancels grants, manages approvers
+    pub governance: Addr,
+    // Escrow contract this treasury collects its fee share from
+    pub escrow: Option<Addr>,
+    // Review milestone evidence; `threshold` approvals release a tranche
+    pub approvers: Vec<Addr>,
+    pub threshold: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Grant {
+    pub grantee: Addr,
+    pub title: String,
+    pub denom: String,
+    pub milestones: Vec<Milestone>,
+    pub status: GrantStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Milestone {
+    pub description: String,
+    pub amount: Uint128,
+    // Hash of the deliverables the grantee points to
+    pub evidence: Option<String>,
+    pub approvals: Vec<Addr>,
+    pub status: MilestoneStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantStatus {
+    Active,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Paid,
+}
+
+// Storage
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const GRANTS: Map<u64, Grant> = Map::new("grants");
+pub const GRANT_COUNT: Item<u64> = Item::new("grant_count");
@@ -0,0 +1,324 @@
+use cosmwasm_std::{
+    entry_point, from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, ListVestingsResponse, QueryMsg, ReceiveMsg,
+    VestingResponse,
+};
+use crate::state::{Config, VestingSchedule, CONFIG, VESTINGS};
+
+const CONTRACT_NAME: &str = "crates.io:phoenix-vesting";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        token: deps.api.addr_validate(&msg.token)?,
+        governance: deps.api.addr_validate(&msg.governance)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("token", config.token)
+        .add_attribute("governance", config.governance))
+}
+
+#[entry_point]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, info, wrapper),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::Revoke { beneficiary } => execute_revoke(deps, env, info, beneficiary),
+        ExecuteMsg::UpdateGovernance { address } => execute_update_governance(deps, info, address),
+    }
+}
+
+fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token || wrapper.sender != config.governance.as_str() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::CreateVesting { beneficiary, start, cliff, end } => {
+            let beneficiary = deps.api.addr_validate(&beneficiary)?;
+            if wrapper.amount.is_zero() || start > cliff || cliff > end || start == end {
+                return Err(ContractError::InvalidSchedule {});
+            }
+            if VESTINGS.has(deps.storage, &beneficiary) {
+                return Err(ContractError::ScheduleExists {});
+            }
+
+            let schedule = VestingSchedule {
+                total: wrapper.amount,
+                claimed: Uint128::zero(),
+                start,
+                cliff,
+                end,
+                revoked_at: None,
+            };
+            VESTINGS.save(deps.storage, &beneficiary, &schedule)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "create_vesting")
+                .add_attribute("beneficiary", beneficiary)
+                .add_attribute("total", wrapper.amount)
+                .add_attribute("start", start.to_string())
+                .add_attribute("cliff", cliff.to_string())
+                .add_attribute("end", end.to_string()))
+        }
+    }
+}
+
+fn execute_claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut schedule = VESTINGS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::ScheduleNotFound {})?;
+
+    let claimable = vested_at(&schedule, env.block.time.seconds()) - schedule.claimed;
+    if claimable.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    schedule.claimed += claimable;
+    VESTINGS.save(deps.storage, &info.sender, &schedule)?;
+
+    Ok(Response::new()
+        .add_message(transfer(&config.token, &info.sender, claimable)?)
+        .add_attribute("action", "claim")
+        .add_attribute("beneficiary", info.sender)
+        .add_attribute("amount", claimable))
+}
+
+// Vested tokens stay claimable by the beneficiary
+fn execute_revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    beneficiary: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.governance {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let mut schedule = VESTINGS
+        .may_load(deps.storage, &beneficiary)?
+        .ok_or(ContractError::ScheduleNotFound {})?;
+    if schedule.revoked_at.is_some() {
+        return Err(ContractError::AlreadyRevoked {});
+    }
+
+    let now = env.block.time.seconds();
+    let unvested = schedule.total - vested_at(&schedule, now);
+    schedule.revoked_at = Some(now);
+    VESTINGS.save(deps.storage, &beneficiary, &schedule)?;
+
+    let mut response = Response::new();
+    if !unvested.is_zero() {
+        response = response.add_message(transfer(&config.token, &config.governance, unvested)?);
+    }
+
+    Ok(response
+        .add_attribute("action", "revoke")
+        .add_attribute("beneficiary", beneficiary)
+        .add_attribute("unvested", unvested))
+}
+
+fn execute_update_governance(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.governance {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.governance = deps.api.addr_validate(&address)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_governance")
+        .add_attribute("governance", config.governance))
+}
+
+fn transfer(token: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })
+}
+
+// Amount vested at `time`, frozen at the revocation time if revoked
+pub fn vested_at(schedule: &VestingSchedule, time: u64) -> Uint128 {
+    let time = match schedule.revoked_at {
+        Some(revoked_at) => time.min(revoked_at),
+        None => time,
+    };
+    if time < schedule.cliff {
+        return Uint128::zero();
+    }
+    if time >= schedule.end {
+        return schedule.total;
+    }
+    schedule
+        .total
+        .multiply_ratio(time - schedule.start, schedule.end - schedule.start)
+}
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&ConfigResponse {
+                token: config.token,
+                governance: config.governance,
+            })
+        }
+        QueryMsg::Vesting { beneficiary, at } => {
+            let beneficiary = deps.api.addr_validate(&beneficiary)?;
+            let schedule = VESTINGS.load(deps.storage, &beneficiary)?;
+            let at = at.unwrap_or_else(|| env.block.time.seconds());
+            to_json_binary(&vesting_response(beneficiary, schedule, at))
+        }
+        QueryMsg::ListVestings { start_after, limit, at } => {
+            let limit = limit.unwrap_or(10).min(30) as usize;
+            let start_after = start_after
+                .map(|address| deps.api.addr_validate(&address))
+                .transpose()?;
+            let start = start_after.as_ref().map(Bound::exclusive);
+            let at = at.unwrap_or_else(|| env.block.time.seconds());
+
+            let vestings = VESTINGS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|(beneficiary, schedule)| vesting_response(beneficiary, schedule, at))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&ListVestingsResponse { vestings })
+        }
+    }
+}
+
+fn vesting_response(beneficiary: Addr, schedule: VestingSchedule, at: u64) -> VestingResponse {
+    let vested = vested_at(&schedule, at);
+    VestingResponse {
+        beneficiary,
+        claimable: vested.saturating_sub(schedule.claimed),
+        vested,
+        schedule,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::OwnedDeps;
+
+    const YEAR: u64 = 365 * 24 * 3600;
+
+    fn create_vesting(deps: DepsMut, start: u64) {
+        let msg = ReceiveMsg::CreateVesting {
+            beneficiary: "founder".to_string(),
+            start,
+            cliff: start + YEAR,
+            end: start + 4 * YEAR,
+        };
+        let wrapper = Cw20ReceiveMsg {
+            sender: "governance".to_string(),
+            amount: Uint128::new(4_000),
+            msg: to_json_binary(&msg).unwrap(),
+        };
+        execute(deps, mock_env(), mock_info("phnx", &[]), ExecuteMsg::Receive(wrapper)).unwrap();
+    }
+
+    fn setup() -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, u64) {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            token: "phnx".to_string(),
+            governance: "governance".to_string(),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        let start = mock_env().block.time.seconds();
+        create_vesting(deps.as_mut(), start);
+        (deps, start)
+    }
+
+    #[test]
+    fn test_cliff_and_linear_vesting() {
+        let (mut deps, start) = setup();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(YEAR - 1);
+        let res = execute(deps.as_mut(), env.clone(), mock_info("founder", &[]), ExecuteMsg::Claim {});
+        assert_eq!(res, Err(ContractError::NothingToClaim {}));
+
+        // The cliff releases the first year at once
+        env.block.time = env.block.time.plus_seconds(1);
+        let res = execute(deps.as_mut(), env.clone(), mock_info("founder", &[]), ExecuteMsg::Claim {})
+            .unwrap();
+        assert_eq!(res.attributes[2].value, "1000");
+
+        let query = QueryMsg::Vesting {
+            beneficiary: "founder".to_string(),
+            at: Some(start + 2 * YEAR),
+        };
+        let vesting: VestingResponse = from_json(super::query(deps.as_ref(), env, query).unwrap())
+            .unwrap();
+        assert_eq!(vesting.vested, Uint128::new(2_000));
+        assert_eq!(vesting.claimable, Uint128::new(1_000));
+    }
+
+    #[test]
+    fn test_revoke_returns_unvested() {
+        let (mut deps, _) = setup();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(2 * YEAR);
+        let revoke = ExecuteMsg::Revoke { beneficiary: "founder".to_string() };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("founder", &[]), revoke.clone());
+        assert_eq!(res, Err(ContractError::Unauthorized {}));
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("governance", &[]), revoke).unwrap();
+        assert_eq!(res.attributes[2].value, "2000");
+
+        // What had vested stays claimable, nothing more accrues
+        env.block.time = env.block.time.plus_seconds(YEAR);
+        let res = execute(deps.as_mut(), env, mock_info("founder", &[]), ExecuteMsg::Claim {})
+            .unwrap();
+        assert_eq!(res.attributes[2].value, "2000");
+    }
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+    
+    #[error("Invalid vesting schedule")]
+    InvalidSchedule {},
+    
+    #[error("Beneficiary already has a vesting schedule")]
+    ScheduleExists {},
+    
+    #[error("Vesting schedule not found")]
+    ScheduleNotFound {},
+    
+    #[error("Vesting schedule already revoked")]
+    AlreadyRevoked {},
+    
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+}
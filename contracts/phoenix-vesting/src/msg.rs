@@ -0,0 +1,74 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::VestingSchedule;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub token: String,
+    pub governance: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Governance funds schedules by sending tokens with a ReceiveMsg
+    Receive(Cw20ReceiveMsg),
+    Claim {},
+    // Returns the unvested remainder to governance
+    Revoke {
+        beneficiary: String,
+    },
+    UpdateGovernance {
+        address: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    // Times are unix seconds; the amount sent is the total vested
+    CreateVesting {
+        beneficiary: String,
+        start: u64,
+        cliff: u64,
+        end: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    // Balances as of `at` (defaults to the current block time)
+    Vesting {
+        beneficiary: String,
+        at: Option<u64>,
+    },
+    ListVestings {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        at: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub token: Addr,
+    pub governance: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingResponse {
+    pub beneficiary: Addr,
+    pub schedule: VestingSchedule,
+    pub vested: Uint128,
+    pub claimable: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListVestingsResponse {
+    pub vestings: Vec<VestingResponse>,
+}
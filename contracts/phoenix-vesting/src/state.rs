@@ -0,0 +1,29 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    // CW20 being vested (PHNX)
+    pub token: Addr,
+    // Creates and revokes schedules; receives revoked tokens
+    pub governance: Addr,
+}
+
+// Nothing vests before the cliff; from then on the total vests linearly
+// from start to end, so the cliff releases everything accrued since start
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    pub total: Uint128,
+    pub claimed: Uint128,
+    pub start: u64,
+    pub cliff: u64,
+    pub end: u64,
+    // Vesting stops here; the unvested part went back to governance
+    pub revoked_at: Option<u64>,
+}
+
+// Storage
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const VESTINGS: Map<&Addr, VestingSchedule> = Map::new("vestings");